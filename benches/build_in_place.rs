@@ -0,0 +1,65 @@
+//! Test bench comparing building a large block by joining blocks with
+//! building it in place.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use text_block_layout::{self, Block};
+
+fn build_in_place(c: &mut Criterion) {
+    let line: Block = "lorem ipsum".into();
+
+    let mut group = c.benchmark_group("build_1000_lines");
+    group.bench_function("stack_left", |b| {
+        b.iter(|| {
+            let mut block = Block::empty();
+            for _ in 0..1000 {
+                block = block.stack_left(&line);
+            }
+            block
+        })
+    });
+    group.bench_function("append_below", |b| {
+        b.iter(|| {
+            let mut block = Block::empty();
+            for _ in 0..1000 {
+                block.append_below(&line);
+            }
+            block
+        })
+    });
+    group.bench_function("push_line", |b| {
+        b.iter(|| {
+            let mut block = Block::empty();
+            for _ in 0..1000 {
+                block.push_line("lorem ipsum");
+            }
+            block
+        })
+    });
+    group.finish();
+
+    let column: Block = Block::of("a\nb\nc");
+
+    let mut group = c.benchmark_group("build_1000_columns");
+    group.bench_function("beside_top", |b| {
+        b.iter(|| {
+            let mut block = Block::empty();
+            for _ in 0..1000 {
+                block = block.beside_top(&column);
+            }
+            block
+        })
+    });
+    group.bench_function("append_right", |b| {
+        b.iter(|| {
+            let mut block = Block::empty();
+            for _ in 0..1000 {
+                block.append_right(&column);
+            }
+            block
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, build_in_place);
+criterion_main!(benches);
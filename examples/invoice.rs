@@ -1,4 +1,4 @@
-use text_block_layout::Block;
+use text_block_layout::{Block, BorderStyle, Column, DecimalAlign, HAlign, Stroke, Table};
 
 struct Item {
     description: String,
@@ -58,18 +58,40 @@ fn info(left_column: usize, title: &str, content_lines: &[String]) -> Block {
     left.pad_right(1).beside_top(&right)
 }
 
+fn dollars(value: f64) -> Block {
+    Block::of(format!["$ {:.2}", value])
+}
+
 fn money(value: f64, width: usize) -> Block {
-    Block::of(format!["$ {:.2}", value]).pad_to_width_left(width)
+    dollars(value).pad_to_width_left(width)
 }
 
-/// Create item specification with columns description (36), unit price (12),
-/// quantity (10) and ammount (12)   
-fn item_line(item: &Item) -> Block {
-    let desc = Block::of(&item.description).pad_to_width_right(36);
-    let unit = money(item.unit_price, 12);
-    let quant = Block::of(item.quantity).pad_to_width_left(10);
-    let amnt = money(item.ammount(), 12);
-    desc.beside_top(&unit).beside_top(&quant).beside_top(&amnt)
+/// Create item specification with columns description, unit price, quantity
+/// and ammount.
+fn item_table(items: &[Item]) -> Table {
+    let number_column = |width| Column::new().align(HAlign::Right).min_width(width);
+    let money_column = |width| number_column(width).decimal(DecimalAlign::new());
+
+    let table = Table::new()
+        .column(Column::new().min_width(36).max_width(36))
+        .column(money_column(11))
+        .column(number_column(9))
+        .column(money_column(11))
+        .border(
+            BorderStyle::none()
+                .header(Some(Stroke::Light))
+                .bottom(Some(Stroke::Light)),
+        )
+        .header(vec!["DESCRIPTION", "UNIT PRICE", "QUANTITY", "AMMOUNT"]);
+
+    items.iter().fold(table, |table, item| {
+        table.row(vec![
+            Block::wrap(&item.description, 36),
+            dollars(item.unit_price),
+            Block::of(item.quantity),
+            dollars(item.ammount()),
+        ])
+    })
 }
 
 fn create_text_invoice(i: &Invoice) -> Block {
@@ -100,23 +122,7 @@ fn create_text_invoice(i: &Invoice) -> Block {
     let addresses = bill_address.in_front_of(&ship_address.pad_left(right_column));
 
     // Specification -----------------------------------------------------------
-    let hline = Block::of_height(1).fill_right(page_width, '─');
-
-    let item_header = Block::of("DESCRIPTION")
-        .pad_to_width_right(36)
-        .beside_top(&Block::of("UNIT PRICE").pad_to_width_left(12))
-        .beside_top(&Block::of("QUANTITY").pad_to_width_left(10))
-        .beside_top(&Block::of("AMMOUNT").pad_to_width_left(12));
-
-    let items = i
-        .items
-        .iter()
-        .fold(Block::empty(), |acc, item| acc.stack_left(&item_line(item)));
-
-    let spec = item_header
-        .stack_left(&hline)
-        .stack_left(&items)
-        .stack_left(&hline);
+    let spec = item_table(&i.items).to_block();
 
     // Totals ------------------------------------------------------------------
     let totals_width: usize = 22;
@@ -1,4 +1,4 @@
-use text_block_layout::Block;
+use text_block_layout::{Block, BorderStyle};
 
 /// Create a square with given border fill, size and position, as a block.
 fn square(border: char, width: usize, offset_left: usize, offset_top: usize) -> Block {
@@ -25,4 +25,18 @@ fn main() {
     println!("Blocks can be put on top of each other, with transparency!");
     println!();
     println!("{}", frontmost.in_front_of(&backmost));
+    println!();
+
+    let front = Block::of_height(3)
+        .pad_right(5)
+        .framed(BorderStyle::single());
+    let back = Block::of_height(4)
+        .pad_right(8)
+        .framed(BorderStyle::double())
+        .pad_left(3)
+        .pad_top(2);
+
+    println!("Box-drawing lines can be merged where blocks overlap!");
+    println!();
+    println!("{}", front.in_front_of_merging_lines(&back));
 }
@@ -0,0 +1,57 @@
+/// Horizontal alignment of blocks, used when padding blocks to a width or
+/// when stacking blocks of different widths on top of each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HAlign {
+    /// Align left sides, padding on the right side.
+    Left,
+    /// Align right sides, padding on the left side.
+    Right,
+    /// Align centers. If padding needs to be uneven, there will be more
+    /// padding on the right side, i.e. leaning to the left.
+    CenterLeft,
+    /// Align centers. If padding needs to be uneven, there will be more
+    /// padding on the left side, i.e. leaning to the right.
+    CenterRight,
+}
+
+/// Vertical alignment of blocks, used when padding blocks to a height or
+/// when joining blocks of different heights beside each other.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VAlign {
+    /// Align top sides, padding on the bottom side.
+    Top,
+    /// Align bottom sides, padding on the top side.
+    Bottom,
+    /// Align centers. If padding needs to be uneven, there will be more
+    /// padding on the bottom side, i.e. leaning to the top.
+    CenterTop,
+    /// Align centers. If padding needs to be uneven, there will be more
+    /// padding on the top side, i.e. leaning to the bottom.
+    CenterBottom,
+}
+
+impl HAlign {
+    /// Split given padding into padding on the left and right side.
+    pub(crate) fn split(self, padding: usize) -> (usize, usize) {
+        let half = padding / 2;
+        match self {
+            HAlign::Left => (0, padding),
+            HAlign::Right => (padding, 0),
+            HAlign::CenterLeft => (half, padding - half),
+            HAlign::CenterRight => (padding - half, half),
+        }
+    }
+}
+
+impl VAlign {
+    /// Split given padding into padding on the top and bottom side.
+    pub(crate) fn split(self, padding: usize) -> (usize, usize) {
+        let half = padding / 2;
+        match self {
+            VAlign::Top => (0, padding),
+            VAlign::Bottom => (padding, 0),
+            VAlign::CenterTop => (half, padding - half),
+            VAlign::CenterBottom => (padding - half, half),
+        }
+    }
+}
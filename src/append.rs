@@ -0,0 +1,124 @@
+use crate::{line_width, repeat, split_lines, Block};
+
+impl Block {
+    /// Pad all lines on the right side so that given width is reached.
+    pub(crate) fn widen(&mut self, width: usize) {
+        if width > self.width {
+            let padding = repeat(' ', width - self.width);
+            for line in self.lines_mut() {
+                line.push_str(&padding);
+            }
+            self.width = width;
+        }
+    }
+
+    /// Add text as new lines at the bottom of block, in place. Text is split
+    /// into lines like in [Block::of]. The block is widened if the text is
+    /// wider than the block.
+    ///
+    /// Lines are pushed onto the block when they are not shared with other
+    /// blocks, otherwise the text is joined below like in [Block::add_text].
+    /// The existing lines are not copied in either case.
+    pub fn push_line(&mut self, text: &str) {
+        for line in split_lines(text) {
+            let width = line_width(line);
+            if width > self.width || self.lines.get_mut().is_none() {
+                self.append_below(&Block::of(line));
+                continue;
+            }
+            let line = line.to_string() + &repeat(' ', self.width - width);
+            self.lines_mut().push(line);
+        }
+    }
+
+    /// Add given block at the bottom of block, in place, aligning the left
+    /// side of the blocks. Same result as [Block::stack_left].
+    ///
+    /// Lines are pushed onto the block when they are not shared with other
+    /// blocks and the block is at least as wide, otherwise the blocks are
+    /// joined like in [Block::stack_left]. The existing lines are not copied
+    /// in either case.
+    pub fn append_below(&mut self, below: &Block) {
+        if below.width > self.width || self.lines.get_mut().is_none() {
+            *self = self.stack_left(below);
+            return;
+        }
+        let padding = repeat(' ', self.width - below.width);
+        self.lines_mut()
+            .extend(below.lines.iter().map(|line| line.clone() + &padding));
+    }
+
+    /// Add given block on the right side of block, in place, aligning the
+    /// top of the blocks. Same result as [Block::beside_top].
+    ///
+    /// Lines are extended in place when they are not shared with other
+    /// blocks, otherwise the blocks are joined like in [Block::beside_top].
+    /// The existing lines are not copied in either case.
+    pub fn append_right(&mut self, right: &Block) {
+        if self.lines.get_mut().is_none() {
+            *self = self.beside_top(right);
+            return;
+        }
+        if self.height() < right.height() {
+            let empty = repeat(' ', self.width);
+            self.lines_mut().resize(right.height(), empty);
+        }
+
+        let empty = repeat(' ', right.width);
+        for (index, line) in self.lines_mut().iter_mut().enumerate() {
+            line.push_str(right.lines.get(index).unwrap_or(&empty));
+        }
+        self.width += right.width;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn push_lines_in_place() {
+        let mut b = Block::empty();
+        b.push_line("ab");
+        b.push_line("c\nde");
+        b.push_line("fgh");
+
+        assert_eq!(Block::of("ab\nc\nde\nfgh"), b);
+        assert_eq!(3, b.width());
+    }
+
+    #[test]
+    fn append_below_in_place() {
+        let a = Block::of("a\nbb");
+        let b = Block::of("ccc");
+        let mut c = a.clone();
+        c.append_below(&b);
+        c.append_below(&a);
+
+        assert_eq!(a.stack_left(&b).stack_left(&a), c);
+    }
+
+    #[test]
+    fn append_right_in_place() {
+        let a = Block::of("a\nbb");
+        let b = Block::of("ccc\nd\ne");
+        let mut c = a.clone();
+        c.append_right(&b);
+        c.append_right(&a);
+
+        assert_eq!(a.beside_top(&b).beside_top(&a), c);
+    }
+
+    #[test]
+    fn append_to_shared_lines() {
+        let a = Block::of("a\nbb");
+        let mut b = a.clone();
+        b.append_below(&a);
+        b.push_line("c");
+        b.append_right(&a);
+
+        assert_eq!(Block::of("a\nbb"), a);
+        assert!(b.lines.get_mut().is_none());
+        assert_eq!(Block::of("a a\nbbbb\na\nbb\nc"), b);
+    }
+}
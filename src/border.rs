@@ -0,0 +1,216 @@
+use crate::box_drawing::Arms;
+
+/// Kind of line used when drawing borders and rules with box-drawing
+/// characters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stroke {
+    /// Thin single line, e.g. `─` and `│`.
+    Light,
+    /// Thick single line, e.g. `━` and `┃`.
+    Heavy,
+    /// Double line, e.g. `═` and `║`.
+    Double,
+}
+
+/// Set of characters used to draw lines of a [BorderStyle].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Glyphs {
+    /// Unicode box-drawing characters, e.g. `┌─┬─┐`.
+    BoxDrawing,
+    /// Unicode box-drawing characters with rounded corners, e.g. `╭─┬─╮`.
+    Rounded,
+    /// ASCII characters, e.g. `+-+-+`.
+    Ascii,
+    /// Characters used in Markdown tables, e.g. `|---|---|`.
+    Markdown,
+}
+
+/// Style of borders and rules drawn around and between parts of for example a
+/// [Table](crate::Table).
+///
+/// A style consists of the characters to draw lines with, and which lines to
+/// draw with what stroke. Junctions where lines meet are drawn with the
+/// matching characters, e.g. `┼`, `├` or `╪`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BorderStyle {
+    glyphs: Glyphs,
+    pub(crate) top: Option<Stroke>,
+    pub(crate) bottom: Option<Stroke>,
+    pub(crate) left: Option<Stroke>,
+    pub(crate) right: Option<Stroke>,
+    pub(crate) header: Option<Stroke>,
+    pub(crate) rows: Option<Stroke>,
+    pub(crate) columns: Option<Stroke>,
+}
+
+impl BorderStyle {
+    /// Style with all lines of given stroke, except rules between rows.
+    fn of_stroke(glyphs: Glyphs, stroke: Stroke) -> Self {
+        BorderStyle {
+            glyphs,
+            top: Some(stroke),
+            bottom: Some(stroke),
+            left: Some(stroke),
+            right: Some(stroke),
+            header: Some(stroke),
+            rows: None,
+            columns: Some(stroke),
+        }
+    }
+
+    /// Style without any lines.
+    pub fn none() -> Self {
+        BorderStyle {
+            glyphs: Glyphs::BoxDrawing,
+            top: None,
+            bottom: None,
+            left: None,
+            right: None,
+            header: None,
+            rows: None,
+            columns: None,
+        }
+    }
+
+    /// Style with light lines, e.g. `┌─┬─┐`.
+    pub fn single() -> Self {
+        BorderStyle::of_stroke(Glyphs::BoxDrawing, Stroke::Light)
+    }
+
+    /// Style with double lines, e.g. `╔═╦═╗`.
+    pub fn double() -> Self {
+        BorderStyle::of_stroke(Glyphs::BoxDrawing, Stroke::Double)
+    }
+
+    /// Style with heavy lines, e.g. `┏━┳━┓`.
+    pub fn heavy() -> Self {
+        BorderStyle::of_stroke(Glyphs::BoxDrawing, Stroke::Heavy)
+    }
+
+    /// Style with light lines and rounded corners, e.g. `╭─┬─╮`.
+    pub fn rounded() -> Self {
+        BorderStyle::of_stroke(Glyphs::Rounded, Stroke::Light)
+    }
+
+    /// Style with ASCII characters, e.g. `+-+-+`. Double and heavy
+    /// horizontal lines are drawn with `=`, like the header rule.
+    pub fn ascii() -> Self {
+        BorderStyle::of_stroke(Glyphs::Ascii, Stroke::Light).header(Some(Stroke::Double))
+    }
+
+    /// Style of Markdown tables, e.g. `| a | b |` and `|---|---|`, without
+    /// top and bottom lines.
+    pub fn markdown() -> Self {
+        BorderStyle::of_stroke(Glyphs::Markdown, Stroke::Light)
+            .top(None)
+            .bottom(None)
+    }
+
+    /// Set stroke of all four outer lines, or `None` for no outer lines.
+    pub fn frame(self, stroke: Option<Stroke>) -> Self {
+        self.top(stroke).bottom(stroke).left(stroke).right(stroke)
+    }
+
+    /// Set stroke of the outer top line.
+    pub fn top(mut self, stroke: Option<Stroke>) -> Self {
+        self.top = stroke;
+        self
+    }
+
+    /// Set stroke of the outer bottom line.
+    pub fn bottom(mut self, stroke: Option<Stroke>) -> Self {
+        self.bottom = stroke;
+        self
+    }
+
+    /// Set stroke of the outer left line.
+    pub fn left(mut self, stroke: Option<Stroke>) -> Self {
+        self.left = stroke;
+        self
+    }
+
+    /// Set stroke of the outer right line.
+    pub fn right(mut self, stroke: Option<Stroke>) -> Self {
+        self.right = stroke;
+        self
+    }
+
+    /// Set stroke of the rule below a header.
+    pub fn header(mut self, stroke: Option<Stroke>) -> Self {
+        self.header = stroke;
+        self
+    }
+
+    /// Set stroke of the rules between rows.
+    pub fn rows(mut self, stroke: Option<Stroke>) -> Self {
+        self.rows = stroke;
+        self
+    }
+
+    /// Set stroke of the rules between columns.
+    pub fn columns(mut self, stroke: Option<Stroke>) -> Self {
+        self.columns = stroke;
+        self
+    }
+
+    /// Return character drawing lines with given arms in this style.
+    pub(crate) fn glyph(&self, arms: Arms) -> char {
+        match self.glyphs {
+            Glyphs::BoxDrawing => arms.to_char(),
+            Glyphs::Rounded => match arms.to_char() {
+                '┌' => '╭',
+                '┐' => '╮',
+                '┘' => '╯',
+                '└' => '╰',
+                c => c,
+            },
+            Glyphs::Ascii if arms.has_vertical() && arms.has_horizontal() => '+',
+            Glyphs::Ascii if arms.has_vertical() => '|',
+            Glyphs::Ascii if arms.left == Some(Stroke::Light) => '-',
+            Glyphs::Ascii if arms.right == Some(Stroke::Light) => '-',
+            Glyphs::Ascii if arms.has_horizontal() => '=',
+            Glyphs::Markdown if arms.has_vertical() => '|',
+            Glyphs::Markdown if arms.has_horizontal() => '-',
+            Glyphs::Ascii | Glyphs::Markdown => ' ',
+        }
+    }
+
+    /// Return character of a horizontal line of given stroke in this style.
+    pub(crate) fn horizontal(&self, stroke: Option<Stroke>) -> char {
+        self.glyph(Arms::horizontal(stroke))
+    }
+
+    /// Return character of a vertical line of given stroke in this style.
+    pub(crate) fn vertical(&self, stroke: Option<Stroke>) -> char {
+        self.glyph(Arms::vertical(stroke))
+    }
+}
+
+impl Default for BorderStyle {
+    fn default() -> Self {
+        BorderStyle::none()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn corner(style: BorderStyle) -> char {
+        style.glyph(Arms {
+            right: Some(Stroke::Light),
+            down: Some(Stroke::Light),
+            ..Arms::default()
+        })
+    }
+
+    #[test]
+    fn glyphs_of_styles() {
+        assert_eq!('┌', corner(BorderStyle::single()));
+        assert_eq!('╭', corner(BorderStyle::rounded()));
+        assert_eq!('+', corner(BorderStyle::ascii()));
+        assert_eq!('|', corner(BorderStyle::markdown()));
+        assert_eq!('=', BorderStyle::ascii().horizontal(Some(Stroke::Double)));
+        assert_eq!('═', BorderStyle::single().horizontal(Some(Stroke::Double)));
+    }
+}
@@ -0,0 +1,292 @@
+//! Lookup between box-drawing characters and the lines, or arms, they
+//! consist of.
+
+use crate::Stroke;
+
+/// The arms of a box-drawing character, i.e. the lines going out from the
+/// center of the character in each direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub(crate) struct Arms {
+    pub up: Option<Stroke>,
+    pub right: Option<Stroke>,
+    pub down: Option<Stroke>,
+    pub left: Option<Stroke>,
+}
+
+/// Box-drawing characters with their arms in the order up, right, down and
+/// left, where `.` is no arm, `l` is light, `h` is heavy and `d` is double.
+const CHARACTERS: &[(char, &str)] = &[
+    ('─', ".l.l"),
+    ('━', ".h.h"),
+    ('│', "l.l."),
+    ('┃', "h.h."),
+    ('┌', ".ll."),
+    ('┍', ".hl."),
+    ('┎', ".lh."),
+    ('┏', ".hh."),
+    ('┐', "..ll"),
+    ('┑', "..lh"),
+    ('┒', "..hl"),
+    ('┓', "..hh"),
+    ('└', "ll.."),
+    ('┕', "lh.."),
+    ('┖', "hl.."),
+    ('┗', "hh.."),
+    ('┘', "l..l"),
+    ('┙', "l..h"),
+    ('┚', "h..l"),
+    ('┛', "h..h"),
+    ('├', "lll."),
+    ('┝', "lhl."),
+    ('┞', "hll."),
+    ('┟', "llh."),
+    ('┠', "hlh."),
+    ('┡', "hhl."),
+    ('┢', "lhh."),
+    ('┣', "hhh."),
+    ('┤', "l.ll"),
+    ('┥', "l.lh"),
+    ('┦', "h.ll"),
+    ('┧', "l.hl"),
+    ('┨', "h.hl"),
+    ('┩', "h.lh"),
+    ('┪', "l.hh"),
+    ('┫', "h.hh"),
+    ('┬', ".lll"),
+    ('┭', ".llh"),
+    ('┮', ".hll"),
+    ('┯', ".hlh"),
+    ('┰', ".lhl"),
+    ('┱', ".lhh"),
+    ('┲', ".hhl"),
+    ('┳', ".hhh"),
+    ('┴', "ll.l"),
+    ('┵', "ll.h"),
+    ('┶', "lh.l"),
+    ('┷', "lh.h"),
+    ('┸', "hl.l"),
+    ('┹', "hl.h"),
+    ('┺', "hh.l"),
+    ('┻', "hh.h"),
+    ('┼', "llll"),
+    ('┽', "lllh"),
+    ('┾', "lhll"),
+    ('┿', "lhlh"),
+    ('╀', "hlll"),
+    ('╁', "llhl"),
+    ('╂', "hlhl"),
+    ('╃', "hllh"),
+    ('╄', "hhll"),
+    ('╅', "llhh"),
+    ('╆', "lhhl"),
+    ('╇', "hhlh"),
+    ('╈', "lhhh"),
+    ('╉', "hlhh"),
+    ('╊', "hhhl"),
+    ('╋', "hhhh"),
+    ('═', ".d.d"),
+    ('║', "d.d."),
+    ('╒', ".dl."),
+    ('╓', ".ld."),
+    ('╔', ".dd."),
+    ('╕', "..ld"),
+    ('╖', "..dl"),
+    ('╗', "..dd"),
+    ('╘', "ld.."),
+    ('╙', "dl.."),
+    ('╚', "dd.."),
+    ('╛', "l..d"),
+    ('╜', "d..l"),
+    ('╝', "d..d"),
+    ('╞', "ldl."),
+    ('╟', "dld."),
+    ('╠', "ddd."),
+    ('╡', "l.ld"),
+    ('╢', "d.dl"),
+    ('╣', "d.dd"),
+    ('╤', ".dld"),
+    ('╥', ".ldl"),
+    ('╦', ".ddd"),
+    ('╧', "ld.d"),
+    ('╨', "dl.l"),
+    ('╩', "dd.d"),
+    ('╪', "ldld"),
+    ('╫', "dldl"),
+    ('╬', "dddd"),
+    ('╴', "...l"),
+    ('╵', "l..."),
+    ('╶', ".l.."),
+    ('╷', "..l."),
+    ('╸', "...h"),
+    ('╹', "h..."),
+    ('╺', ".h.."),
+    ('╻', "..h."),
+    ('╼', ".h.l"),
+    ('╽', "l.h."),
+    ('╾', ".l.h"),
+    ('╿', "h.l."),
+    // Rounded corners last, so that square corners are preferred in lookup
+    ('╭', ".ll."),
+    ('╮', "..ll"),
+    ('╯', "l..l"),
+    ('╰', "ll.."),
+];
+
+/// Parse arms from their short description in the character table.
+fn parse_arms(arms: &str) -> Arms {
+    let mut strokes = arms.chars().map(|c| match c {
+        'l' => Some(Stroke::Light),
+        'h' => Some(Stroke::Heavy),
+        'd' => Some(Stroke::Double),
+        _ => None,
+    });
+
+    Arms {
+        up: strokes.next().flatten(),
+        right: strokes.next().flatten(),
+        down: strokes.next().flatten(),
+        left: strokes.next().flatten(),
+    }
+}
+
+impl Arms {
+    /// Arms of a horizontal line of given stroke.
+    pub fn horizontal(stroke: Option<Stroke>) -> Self {
+        Arms {
+            right: stroke,
+            left: stroke,
+            ..Arms::default()
+        }
+    }
+
+    /// Arms of a vertical line of given stroke.
+    pub fn vertical(stroke: Option<Stroke>) -> Self {
+        Arms {
+            up: stroke,
+            down: stroke,
+            ..Arms::default()
+        }
+    }
+
+    /// Tell if there are no arms at all.
+    pub fn is_empty(&self) -> bool {
+        *self == Arms::default()
+    }
+
+    /// Tell if there is any arm going up or down.
+    pub fn has_vertical(&self) -> bool {
+        self.up.is_some() || self.down.is_some()
+    }
+
+    /// Tell if there is any arm going left or right.
+    pub fn has_horizontal(&self) -> bool {
+        self.left.is_some() || self.right.is_some()
+    }
+
+    /// Return the arms of given box-drawing character, if it is one.
+    pub fn of_char(c: char) -> Option<Self> {
+        CHARACTERS
+            .iter()
+            .find(|(character, _)| *character == c)
+            .map(|(_, arms)| parse_arms(arms))
+    }
+
+    /// Replace all arms of one stroke with another stroke.
+    fn replace(self, from: Stroke, to: Stroke) -> Self {
+        let replace = |arm| if arm == Some(from) { Some(to) } else { arm };
+        Arms {
+            up: replace(self.up),
+            right: replace(self.right),
+            down: replace(self.down),
+            left: replace(self.left),
+        }
+    }
+
+    /// Return the box-drawing character with exactly these arms, if there is
+    /// one.
+    fn exact_char(self) -> Option<char> {
+        CHARACTERS
+            .iter()
+            .find(|(_, arms)| parse_arms(arms) == self)
+            .map(|(character, _)| *character)
+    }
+
+    /// Return the box-drawing character best matching these arms. Since not
+    /// all combinations of strokes exist as characters, heavy and double
+    /// arms are drawn as light arms when needed. No arms at all gives a
+    /// space.
+    pub fn to_char(self) -> char {
+        if self.is_empty() {
+            return ' ';
+        }
+
+        let without_heavy = self.replace(Stroke::Heavy, Stroke::Light);
+        let without_double = self.replace(Stroke::Double, Stroke::Light);
+        let all_light = without_heavy.replace(Stroke::Double, Stroke::Light);
+
+        [self, without_double, without_heavy, all_light]
+            .iter()
+            .find_map(|arms| arms.exact_char())
+            .unwrap_or(' ')
+    }
+}
+
+/// Merge box-drawing character in front with box-drawing character behind,
+/// into the character with the arms of both. Where both characters have an
+/// arm in the same direction, the arm in front is used. Returns nothing if
+/// any of the characters is not a box-drawing character.
+pub(crate) fn merge(front: char, behind: char) -> Option<char> {
+    let front_arms = Arms::of_char(front)?;
+    let behind_arms = Arms::of_char(behind)?;
+
+    let arms = Arms {
+        up: front_arms.up.or(behind_arms.up),
+        right: front_arms.right.or(behind_arms.right),
+        down: front_arms.down.or(behind_arms.down),
+        left: front_arms.left.or(behind_arms.left),
+    };
+
+    // Keep character in front as is, if nothing is added, e.g. rounded
+    // corners
+    if arms == front_arms {
+        Some(front)
+    } else {
+        Some(arms.to_char())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_characters_round_trip() {
+        for (c, arms) in CHARACTERS.iter().filter(|(c, _)| !"╭╮╯╰".contains(*c)) {
+            assert_eq!(*c, parse_arms(arms).to_char());
+        }
+    }
+
+    #[test]
+    fn mixed_strokes_fall_back_to_light() {
+        let arms = Arms {
+            up: Some(Stroke::Double),
+            right: Some(Stroke::Heavy),
+            ..Arms::default()
+        };
+
+        assert_eq!('┕', arms.to_char());
+        assert_eq!(' ', Arms::default().to_char());
+    }
+
+    #[test]
+    fn merge_characters() {
+        assert_eq!(Some('┼'), merge('─', '│'));
+        assert_eq!(Some('┬'), merge('┐', '┌'));
+        assert_eq!(Some('╫'), merge('─', '║'));
+        assert_eq!(Some('╪'), merge('═', '│'));
+        assert_eq!(Some('╭'), merge('╭', '┌'));
+        assert_eq!(Some('├'), merge('╭', '╰'));
+        assert_eq!(None, merge('─', 'x'));
+        assert_eq!(None, merge('+', '│'));
+    }
+}
@@ -0,0 +1,137 @@
+use crate::{Block, Compositing};
+
+/// A block placed on a [Canvas].
+#[derive(Clone, Debug)]
+struct Layer {
+    block: Block,
+    x: isize,
+    y: isize,
+    z: i32,
+    compositing: Compositing,
+}
+
+/// Blocks placed at positions in layers, flattened into a single block by
+/// overlaying them in order of their z-index.
+///
+/// Layers with higher z-index are placed in front of layers with lower
+/// z-index. Layers with the same z-index are placed in the order they were
+/// added, with the last one in front. The canvas grows to fit all layers,
+/// and parts of layers at negative positions are clipped away, see
+/// [Block::overlay_at_with].
+///
+/// ```
+/// use text_block_layout::{Block, BorderStyle, Canvas};
+///
+/// let frame = Block::of_height(1).pad_right(5).framed(BorderStyle::single());
+/// let canvas = Canvas::new()
+///     .layer(Block::of("label"), 1, 1, 1)
+///     .layer(frame, 0, 0, 0);
+///
+/// assert_eq!("┌─────┐\n│label│\n└─────┘", canvas.to_block().render());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Canvas {
+    layers: Vec<Layer>,
+}
+
+impl Canvas {
+    /// Create empty canvas.
+    pub fn new() -> Self {
+        Canvas { layers: vec![] }
+    }
+
+    /// Add block with its top left corner at column `x` and line `y`, in
+    /// layer of given z-index. Spaces in the block are transparent.
+    pub fn layer<T: Into<Block>>(self, block: T, x: isize, y: isize, z: i32) -> Self {
+        self.layer_with(block, x, y, z, &Compositing::default())
+    }
+
+    /// Add block with its top left corner at column `x` and line `y`, in
+    /// layer of given z-index, overlaying the layers behind it according to
+    /// given compositing rule.
+    pub fn layer_with<T: Into<Block>>(
+        mut self,
+        block: T,
+        x: isize,
+        y: isize,
+        z: i32,
+        compositing: &Compositing,
+    ) -> Self {
+        self.layers.push(Layer {
+            block: block.into(),
+            x,
+            y,
+            z,
+            compositing: compositing.clone(),
+        });
+        self
+    }
+
+    /// Number of layers added to the canvas.
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Flatten all layers into a single block.
+    pub fn to_block(&self) -> Block {
+        let mut layers = self.layers.iter().collect::<Vec<&Layer>>();
+        layers.sort_by_key(|layer| layer.z);
+
+        let mut canvas = Block::empty();
+        for layer in layers {
+            canvas.place_with(&layer.block, layer.x, layer.y, &layer.compositing);
+        }
+        canvas
+    }
+}
+
+impl From<&Canvas> for Block {
+    fn from(canvas: &Canvas) -> Self {
+        canvas.to_block()
+    }
+}
+
+impl From<Canvas> for Block {
+    fn from(canvas: Canvas) -> Self {
+        canvas.to_block()
+    }
+}
+
+impl std::fmt::Display for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_block())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_canvas() {
+        assert_eq!(Block::empty(), Canvas::new().to_block());
+        assert_eq!(0, Canvas::new().layer_count());
+    }
+
+    #[test]
+    fn layers_in_order_of_z_index() {
+        let canvas = Canvas::new()
+            .layer("aaa", 0, 0, 2)
+            .layer("bbbbb", 1, 0, 1)
+            .layer("ccc", 1, 1, 1)
+            .layer("d", 2, 1, 1);
+
+        assert_eq!(4, canvas.layer_count());
+        assert_eq!(vec!["aaabbb", " cdc  "], *canvas.to_block().lines);
+    }
+
+    #[test]
+    fn layers_with_compositing_and_negative_positions() {
+        let opaque = Compositing::opaque();
+        let canvas = Canvas::new()
+            .layer("12345", 0, 0, 0)
+            .layer_with("a b", -1, 0, 1, &opaque);
+
+        assert_eq!(" b345", canvas.to_block().lines[0]);
+    }
+}
@@ -0,0 +1,223 @@
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// A single display column in a line of a [Block](crate::Block).
+///
+/// Each grapheme cluster, i.e. what a reader perceives as one character,
+/// occupies one or more columns depending on its display width. The first
+/// column of a grapheme cluster holds the grapheme itself and any further
+/// columns of a wide grapheme cluster hold continuation cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cell<'a> {
+    /// Grapheme cluster starting at this column, covering given number of
+    /// columns.
+    Grapheme { text: &'a str, width: usize },
+    /// Column covered by a wide grapheme cluster starting to the left.
+    Continuation,
+}
+
+impl<'a> Cell<'a> {
+    /// Return text of grapheme cluster, or empty string for continuation
+    /// cells.
+    pub fn text(&self) -> &'a str {
+        match self {
+            Cell::Grapheme { text, .. } => text,
+            Cell::Continuation => "",
+        }
+    }
+
+    /// Return number of columns covered by grapheme cluster, or zero for
+    /// continuation cells.
+    pub fn width(&self) -> usize {
+        match self {
+            Cell::Grapheme { width, .. } => *width,
+            Cell::Continuation => 0,
+        }
+    }
+
+    /// Return true if cell is a continuation of a wide grapheme cluster.
+    pub fn is_continuation(&self) -> bool {
+        *self == Cell::Continuation
+    }
+}
+
+/// Display width of a single grapheme cluster.
+pub(crate) fn grapheme_width(grapheme: &str) -> usize {
+    UnicodeWidthStr::width(grapheme)
+}
+
+/// Find the grapheme clusters of line covering the columns `begin..end`,
+/// returning their byte range and column range. Zero-width grapheme clusters
+/// belong to the grapheme cluster before them. The line must be at least
+/// `end` columns wide.
+pub(crate) fn covering(line: &str, begin: usize, end: usize) -> (Range<usize>, Range<usize>) {
+    let mut column = 0;
+    let mut first = None;
+
+    for (index, grapheme) in line.grapheme_indices(true) {
+        let width = grapheme_width(grapheme);
+        if width == 0 {
+            continue;
+        }
+        if column >= end {
+            let (first_index, first_column) = first.unwrap_or((index, column));
+            return (first_index..index, first_column..column);
+        }
+        if first.is_none() && column + width > begin {
+            first = Some((index, column));
+        }
+        column += width;
+    }
+
+    let (first_index, first_column) = first.unwrap_or((line.len(), column));
+    (first_index..line.len(), first_column..column)
+}
+
+/// Split line into exactly `width` cells. Zero-width grapheme clusters are
+/// attached to the preceding grapheme cluster, or dropped at the start of the
+/// line. Missing cells are filled with spaces and grapheme clusters not
+/// fitting within the width are dropped.
+pub(crate) fn line_cells(line: &str, width: usize) -> Vec<Cell<'_>> {
+    let mut result: Vec<Cell> = Vec::with_capacity(width);
+    let mut last_start: Option<(usize, usize)> = None;
+
+    for (begin, grapheme) in line.grapheme_indices(true) {
+        let grapheme_width = grapheme_width(grapheme);
+        let end = begin + grapheme.len();
+
+        if grapheme_width == 0 {
+            // Extend preceding grapheme cluster to also cover this one
+            if let Some((column, text_begin)) = last_start {
+                let width = result[column].width();
+                result[column] = Cell::Grapheme {
+                    text: &line[text_begin..end],
+                    width,
+                };
+            }
+            continue;
+        }
+        if result.len() + grapheme_width > width {
+            break;
+        }
+
+        last_start = Some((result.len(), begin));
+        result.push(Cell::Grapheme {
+            text: grapheme,
+            width: grapheme_width,
+        });
+        for _ in 1..grapheme_width {
+            result.push(Cell::Continuation);
+        }
+    }
+
+    while result.len() < width {
+        result.push(Cell::Grapheme {
+            text: " ",
+            width: 1,
+        });
+    }
+    result
+}
+
+/// Return, for each cell, the column where the grapheme cluster covering the
+/// cell starts.
+pub(crate) fn grapheme_starts(cells: &[Cell]) -> Vec<usize> {
+    let mut start = 0;
+    cells
+        .iter()
+        .enumerate()
+        .map(|(x, cell)| {
+            if !cell.is_continuation() {
+                start = x;
+            }
+            start
+        })
+        .collect()
+}
+
+/// Return text of the columns in the range `begin..end` of given cells. Wide
+/// grapheme clusters only partially inside the range are replaced with
+/// spaces, so that the text always covers exactly the columns of the range.
+pub(crate) fn columns_text(cells: &[Cell], begin: usize, end: usize) -> String {
+    let starts = grapheme_starts(cells);
+    let mut text = String::new();
+
+    for (x, &start) in starts.iter().enumerate().take(end).skip(begin) {
+        if start < begin || start + cells[start].width() > end {
+            text.push(' ');
+        } else if start == x {
+            text.push_str(cells[start].text());
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cells_of_wide_graphemes() {
+        assert_eq!(
+            vec![
+                Cell::Grapheme {
+                    text: "世",
+                    width: 2
+                },
+                Cell::Continuation,
+                Cell::Grapheme {
+                    text: "a",
+                    width: 1
+                },
+            ],
+            line_cells("世a", 3)
+        );
+    }
+
+    #[test]
+    fn cells_keep_grapheme_clusters_together() {
+        let family = "👨\u{200d}👩\u{200d}👧";
+        let flag = "🇸🇪";
+        let accent = "e\u{301}";
+        let line = format!("{}{}{}", family, flag, accent);
+
+        let texts = line_cells(&line, 5)
+            .iter()
+            .map(|cell| cell.text())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![family, "", flag, "", accent], texts);
+    }
+
+    #[test]
+    fn cells_attach_zero_width_grapheme_to_preceding() {
+        let cells = line_cells("\u{200b}a\u{200b}b", 2);
+
+        assert_eq!("a\u{200b}", cells[0].text());
+        assert_eq!("b", cells[1].text());
+    }
+
+    #[test]
+    fn cells_are_padded_and_truncated_to_width() {
+        let texts = |line, width| {
+            line_cells(line, width)
+                .iter()
+                .map(|cell| cell.text())
+                .collect::<String>()
+        };
+
+        assert_eq!("a  ", texts("a", 3));
+        assert_eq!("a ", texts("a世", 2));
+    }
+
+    #[test]
+    fn columns_text_replaces_split_graphemes_with_spaces() {
+        let cells = line_cells("a世b界", 6);
+
+        assert_eq!("a世b界", columns_text(&cells, 0, 6));
+        assert_eq!(" b", columns_text(&cells, 2, 4));
+        assert_eq!("世b ", columns_text(&cells, 1, 5));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::cell::{columns_text, line_cells};
+use crate::{subtract_or_zero, Block};
+
+impl Block {
+    /// Extract the rectangular region of given size with its top left corner
+    /// at column `x` and line `y`. The region is clipped to the block, so the
+    /// result can be smaller than the requested size. Wide characters cut in
+    /// half by the region are replaced with spaces.
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        let begin = x.min(self.width);
+        let end = x.saturating_add(width).min(self.width);
+        let lines = self.lines.iter().skip(y).take(height);
+
+        let lines = if begin == 0 && end == self.width {
+            lines.cloned().collect::<Vec<String>>()
+        } else {
+            lines
+                .map(|line| columns_text(&line_cells(line, self.width), begin, end))
+                .collect::<Vec<String>>()
+        };
+
+        Block::new(end - begin, lines)
+    }
+
+    /// Remove given number of lines from the top of block.
+    pub fn crop_top(&self, height: usize) -> Self {
+        self.crop(0, height, self.width, self.height())
+    }
+
+    /// Remove given number of lines from the bottom of block.
+    pub fn crop_bottom(&self, height: usize) -> Self {
+        self.crop(0, 0, self.width, subtract_or_zero(self.height(), height))
+    }
+
+    /// Remove given number of columns from the left side of block.
+    pub fn crop_left(&self, width: usize) -> Self {
+        self.crop(width, 0, self.width, self.height())
+    }
+
+    /// Remove given number of columns from the right side of block.
+    pub fn crop_right(&self, width: usize) -> Self {
+        self.crop(0, 0, subtract_or_zero(self.width, width), self.height())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn block() -> Block {
+        Block::of("abcd").add_text("efgh").add_text("ijkl")
+    }
+
+    #[test]
+    fn crop_region() {
+        assert_eq!("fg\njk", block().crop(1, 1, 2, 2).render());
+        assert_eq!(Block::of("l"), block().crop(3, 2, 5, 5));
+        assert_eq!(Block::empty(), block().crop(4, 3, 1, 1));
+    }
+
+    #[test]
+    fn crop_sides() {
+        assert_eq!("efgh\nijkl", block().crop_top(1).render());
+        assert_eq!("abcd", block().crop_bottom(2).render());
+        assert_eq!("d\nh\nl", block().crop_left(3).render());
+        assert_eq!("a\ne\ni", block().crop_right(3).render());
+        assert_eq!(0, block().crop_bottom(4).height());
+    }
+
+    #[test]
+    fn crop_is_inverse_of_pad() {
+        let padded = block().pad_left(2).pad_top(3).pad_right(1).pad_bottom(4);
+
+        assert_eq!(
+            block(),
+            padded.crop_left(2).crop_top(3).crop_right(1).crop_bottom(4)
+        );
+    }
+
+    #[test]
+    fn crop_wide_characters() {
+        let b = Block::of("a世b界");
+
+        assert_eq!(vec!["世b"], *b.crop(1, 0, 3, 1).lines);
+        assert_eq!(vec![" b "], *b.crop(2, 0, 3, 1).lines);
+        assert_eq!(vec![" b界"], *b.crop_left(2).lines);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{line_width, of_single_lines, repeat, split_lines, subtract_or_zero, Block};
+
+/// Settings for aligning numbers on their decimal separator, see
+/// [Block::of_decimals] and [Column::decimal](crate::Column::decimal).
+///
+/// Optionally, a prefix or suffix, like a currency symbol, can be given.
+/// Numbers with the prefix or suffix get it aligned in a column of its own,
+/// on the left or right side of the numbers.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DecimalAlign {
+    separator: char,
+    prefix: String,
+    suffix: String,
+}
+
+/// A number split into parts to be aligned.
+struct Parts<'a> {
+    prefix: bool,
+    integer: &'a str,
+    fraction: &'a str,
+    suffix: bool,
+}
+
+impl DecimalAlign {
+    /// Create settings using `.` as decimal separator, without prefix and
+    /// suffix.
+    pub fn new() -> Self {
+        DecimalAlign {
+            separator: '.',
+            prefix: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    /// Set decimal separator, e.g. `,`.
+    pub fn separator(mut self, separator: char) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Set prefix to align separately, e.g. `$ `.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_string();
+        self
+    }
+
+    /// Set suffix to align separately, e.g. ` kr`.
+    pub fn suffix(mut self, suffix: &str) -> Self {
+        self.suffix = suffix.to_string();
+        self
+    }
+
+    /// Split text of a number into its parts.
+    fn parts<'a>(&self, text: &'a str) -> Parts<'a> {
+        let mut number = text.trim();
+
+        let prefix = !self.prefix.is_empty() && number.starts_with(&self.prefix);
+        if prefix {
+            number = number[self.prefix.len()..].trim_start();
+        }
+        let suffix = !self.suffix.is_empty() && number.ends_with(&self.suffix);
+        if suffix {
+            number = number[..number.len() - self.suffix.len()].trim_end();
+        }
+
+        let (integer, fraction) = match number.find(self.separator) {
+            Some(index) => number.split_at(index),
+            None => (number, ""),
+        };
+
+        Parts {
+            prefix,
+            integer,
+            fraction,
+            suffix,
+        }
+    }
+
+    /// Align lines of text containing numbers, so that the decimal
+    /// separators are placed in the same column. All lines get the same
+    /// width.
+    pub(crate) fn align_lines(&self, lines: &[&str]) -> Vec<String> {
+        let parts = lines
+            .iter()
+            .map(|line| self.parts(line))
+            .collect::<Vec<Parts>>();
+
+        let max = |width: &dyn Fn(&Parts) -> usize| parts.iter().map(width).max().unwrap_or(0);
+        let prefix_width = max(&|p| {
+            if p.prefix {
+                line_width(&self.prefix)
+            } else {
+                0
+            }
+        });
+        let integer_width = max(&|p| line_width(p.integer));
+        let fraction_width = max(&|p| line_width(p.fraction));
+        let suffix_width = max(&|p| {
+            if p.suffix {
+                line_width(&self.suffix)
+            } else {
+                0
+            }
+        });
+
+        parts
+            .iter()
+            .map(|p| {
+                let mut line = String::new();
+                if p.prefix {
+                    line += &self.prefix;
+                } else {
+                    line += &repeat(' ', prefix_width);
+                }
+                line += &repeat(' ', subtract_or_zero(integer_width, line_width(p.integer)));
+                line += p.integer;
+                line += p.fraction;
+                line += &repeat(
+                    ' ',
+                    subtract_or_zero(fraction_width, line_width(p.fraction)),
+                );
+                if p.suffix {
+                    line += &self.suffix;
+                } else {
+                    line += &repeat(' ', suffix_width);
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+impl Default for DecimalAlign {
+    fn default() -> Self {
+        DecimalAlign::new()
+    }
+}
+
+impl Block {
+    /// Create block with given numbers stacked on top of each other, aligned
+    /// on their decimal separator according to given settings. Numbers
+    /// without decimal separator are aligned as integers. Text containing
+    /// newlines is split into several lines, like in [Block::of], with each
+    /// line aligned as a number of its own.
+    pub fn of_decimals<I>(numbers: I, align: &DecimalAlign) -> Self
+    where
+        I: IntoIterator,
+        I::Item: ToString,
+    {
+        let texts = numbers
+            .into_iter()
+            .map(|number| number.to_string())
+            .collect::<Vec<String>>();
+        let lines = texts
+            .iter()
+            .flat_map(|text| split_lines(text))
+            .collect::<Vec<&str>>();
+
+        of_single_lines(align.align_lines(&lines))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn align_on_decimal_separator() {
+        let b = Block::of_decimals(vec![1.5, 12.375, 100.0], &DecimalAlign::new());
+
+        assert_eq!(vec!["  1.5  ", " 12.375", "100    "], *b.lines);
+    }
+
+    #[test]
+    fn align_numbers_containing_newlines() {
+        let b = Block::of_decimals(vec!["1\n2", "3.5"], &DecimalAlign::new());
+
+        assert_eq!(3, b.width());
+        assert_eq!(vec!["1  ", "2  ", "3.5"], *b.lines);
+    }
+
+    #[test]
+    fn align_on_custom_separator() {
+        let align = DecimalAlign::new().separator(',');
+        let b = Block::of_decimals(vec!["3,14", "1000", "-2,5"], &align);
+
+        assert_eq!(vec!["   3,14", "1000   ", "  -2,5 "], *b.lines);
+    }
+
+    #[test]
+    fn align_prefix_and_suffix() {
+        let dollars = DecimalAlign::new().prefix("$");
+        let b = Block::of_decimals(vec!["$ 1.5", "$12.25", "7"], &dollars);
+
+        assert_eq!(vec!["$ 1.5 ", "$12.25", "  7   "], *b.lines);
+
+        let crowns = DecimalAlign::new().separator(',').suffix("kr");
+        let b = Block::of_decimals(vec!["1,5 kr", "12,25kr"], &crowns);
+
+        assert_eq!(vec![" 1,5 kr", "12,25kr"], *b.lines);
+    }
+}
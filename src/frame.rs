@@ -0,0 +1,242 @@
+use crate::box_drawing::Arms;
+use crate::{line_width, of_single_lines, repeat, subtract_or_zero, Block, BorderStyle, HAlign};
+
+/// Settings of a border drawn around a block, see [Block::framed_with].
+///
+/// The outer lines of the [BorderStyle] are used, so sides can be left out
+/// by giving them no stroke.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Frame {
+    style: BorderStyle,
+    padding: usize,
+    vertical_padding: usize,
+    title: Option<String>,
+    title_align: HAlign,
+}
+
+impl Frame {
+    /// Create frame of given style, without padding and title.
+    pub fn new(style: BorderStyle) -> Self {
+        Frame {
+            style,
+            padding: 0,
+            vertical_padding: 0,
+            title: None,
+            title_align: HAlign::Left,
+        }
+    }
+
+    /// Set number of spaces between the content and the left and right
+    /// lines.
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Set number of empty lines between the content and the top and bottom
+    /// lines.
+    pub fn vertical_padding(mut self, padding: usize) -> Self {
+        self.vertical_padding = padding;
+        self
+    }
+
+    /// Set title embedded in the top line. The frame is widened if needed
+    /// to fit the title. Newlines in the title are replaced with spaces. The
+    /// title is not shown if the style has no top line.
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.lines().collect::<Vec<&str>>().join(" "));
+        self
+    }
+
+    /// Set placement of the title in the top line. Default is left.
+    pub fn title_align(mut self, align: HAlign) -> Self {
+        self.title_align = align;
+        self
+    }
+
+    /// Return corner character joining given lines, or nothing if any of
+    /// the lines is missing.
+    fn corner(&self, horizontal: Arms, vertical: Arms) -> String {
+        if horizontal.is_empty() || vertical.is_empty() {
+            return String::new();
+        }
+        let arms = Arms {
+            up: vertical.up,
+            right: horizontal.right,
+            down: vertical.down,
+            left: horizontal.left,
+        };
+        self.style.glyph(arms).to_string()
+    }
+
+    /// Draw top line of given inner width, with the title embedded.
+    fn top_line(&self, width: usize) -> String {
+        let style = &self.style;
+        let edge = style.horizontal(style.top);
+        let right = Arms {
+            right: style.top,
+            ..Arms::default()
+        };
+        let left = Arms {
+            left: style.top,
+            ..Arms::default()
+        };
+        let down = |stroke| Arms {
+            down: stroke,
+            ..Arms::default()
+        };
+
+        let mut line = self.corner(right, down(style.left));
+        match &self.title {
+            Some(title) => {
+                let title = format!(" {} ", title);
+                let space = subtract_or_zero(width, line_width(&title) + 2);
+                let (before, after) = self.title_align.split(space);
+                line += &repeat(edge, 1 + before);
+                line += &title;
+                line += &repeat(edge, after + 1);
+            }
+            None => line += &repeat(edge, width),
+        }
+        line + &self.corner(left, down(style.right))
+    }
+
+    /// Draw bottom line of given inner width.
+    fn bottom_line(&self, width: usize) -> String {
+        let style = &self.style;
+        let up = |stroke| Arms {
+            up: stroke,
+            ..Arms::default()
+        };
+        let right = Arms {
+            right: style.bottom,
+            ..Arms::default()
+        };
+        let left = Arms {
+            left: style.bottom,
+            ..Arms::default()
+        };
+
+        self.corner(right, up(style.left))
+            + &repeat(style.horizontal(style.bottom), width)
+            + &self.corner(left, up(style.right))
+    }
+}
+
+impl Block {
+    /// Draw border of given style around block, see [BorderStyle]. Only the
+    /// outer lines of the style are drawn.
+    pub fn framed(&self, style: BorderStyle) -> Self {
+        self.framed_with(&Frame::new(style))
+    }
+
+    /// Draw border around block according to given frame settings, with
+    /// optional padding and title.
+    pub fn framed_with(&self, frame: &Frame) -> Self {
+        let style = &frame.style;
+        let title_width = match (&frame.title, style.top) {
+            (Some(title), Some(_)) => line_width(title) + 4,
+            _ => 0,
+        };
+
+        let inner = self
+            .pad_left(frame.padding)
+            .pad_right(frame.padding)
+            .pad_top(frame.vertical_padding)
+            .pad_bottom(frame.vertical_padding)
+            .pad_to_width(title_width, HAlign::Left);
+
+        let vertical = |stroke| match stroke {
+            Some(_) => style.vertical(stroke).to_string(),
+            None => String::new(),
+        };
+        let (left, right) = (vertical(style.left), vertical(style.right));
+
+        let mut lines = Vec::new();
+        if style.top.is_some() {
+            lines.push(frame.top_line(inner.width));
+        }
+        for line in inner.lines.iter() {
+            lines.push(format!("{}{}{}", left, line, right));
+        }
+        if style.bottom.is_some() {
+            lines.push(frame.bottom_line(inner.width));
+        }
+        of_single_lines(lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Stroke;
+
+    #[test]
+    fn framed_with_style() {
+        let b = Block::of("ab\nc").framed(BorderStyle::single());
+
+        assert_eq!(vec!["┌──┐", "│ab│", "│c │", "└──┘"], *b.lines);
+        assert_eq!(
+            "╭─╮\n│x│\n╰─╯",
+            Block::of('x').framed(BorderStyle::rounded()).render()
+        );
+        assert_eq!(
+            "+-+\n|x|\n+-+",
+            Block::of('x').framed(BorderStyle::ascii()).render()
+        );
+    }
+
+    #[test]
+    fn framed_with_missing_sides() {
+        let style = BorderStyle::double().left(None).bottom(None);
+        let b = Block::of("ab").framed(style);
+
+        assert_eq!(vec!["══╗", "ab║"], *b.lines);
+    }
+
+    #[test]
+    fn framed_with_mixed_strokes() {
+        let style = BorderStyle::single().top(Some(Stroke::Double));
+        let b = Block::of("a").framed(style);
+
+        assert_eq!(vec!["╒═╕", "│a│", "└─┘"], *b.lines);
+    }
+
+    #[test]
+    fn framed_with_padding() {
+        let frame = Frame::new(BorderStyle::single())
+            .padding(2)
+            .vertical_padding(1);
+        let b = Block::of("a").framed_with(&frame);
+
+        assert_eq!(
+            vec!["┌─────┐", "│     │", "│  a  │", "│     │", "└─────┘"],
+            *b.lines
+        );
+    }
+
+    #[test]
+    fn framed_with_title() {
+        let frame = |align| {
+            Frame::new(BorderStyle::single())
+                .title("T")
+                .title_align(align)
+        };
+        let b = Block::of("abcdefghi");
+
+        assert_eq!("┌─ T ─────┐", b.framed_with(&frame(HAlign::Left)).lines[0]);
+        assert_eq!(
+            "┌─── T ───┐",
+            b.framed_with(&frame(HAlign::CenterLeft)).lines[0]
+        );
+        assert_eq!("┌───── T ─┐", b.framed_with(&frame(HAlign::Right)).lines[0]);
+    }
+
+    #[test]
+    fn framed_widened_for_title() {
+        let frame = Frame::new(BorderStyle::single()).title("Title");
+        let b = Block::of("a").framed_with(&frame);
+
+        assert_eq!(vec!["┌─ Title ─┐", "│a        │", "└─────────┘"], *b.lines);
+    }
+}
@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::mem;
+use std::sync::Arc;
+
+use crate::{Block, HAlign, VAlign};
+
+/// Node in the composition tree of a [LazyBlock].
+#[derive(Clone, Debug)]
+enum Node {
+    /// Already computed block, e.g. of text.
+    Text(Block),
+    /// Padding with spaces on each side, in order top, right, bottom and
+    /// left.
+    Pad(LazyBlock, [usize; 4]),
+    /// Fill on the right and bottom side with a filler character.
+    Fill(LazyBlock, usize, usize, char),
+    Beside(LazyBlock, LazyBlock, VAlign),
+    Stack(LazyBlock, LazyBlock, HAlign),
+    /// Front block overlaid in front of back block.
+    Overlay(LazyBlock, LazyBlock),
+}
+
+impl Node {
+    /// Blocks the node is composed of, in order.
+    fn children(&self) -> Vec<&LazyBlock> {
+        match self {
+            Node::Text(_) => vec![],
+            Node::Pad(block, _) | Node::Fill(block, ..) => vec![block],
+            Node::Beside(first, second, _)
+            | Node::Stack(first, second, _)
+            | Node::Overlay(first, second) => vec![first, second],
+        }
+    }
+
+    /// Mutable blocks the node is composed of, in order.
+    fn children_mut(&mut self) -> Vec<&mut LazyBlock> {
+        match self {
+            Node::Text(_) => vec![],
+            Node::Pad(block, _) | Node::Fill(block, ..) => vec![block],
+            Node::Beside(first, second, _)
+            | Node::Stack(first, second, _)
+            | Node::Overlay(first, second) => vec![first, second],
+        }
+    }
+
+    /// Compute the lines of the node, taking the computed blocks of its
+    /// children from the top of given stack.
+    fn compute(&self, computed: &mut Vec<Block>) -> Block {
+        let mut pop = || computed.pop().expect("children are computed first");
+        match self {
+            Node::Text(block) => block.clone(),
+            Node::Pad(_, [top, right, bottom, left]) => pop()
+                .pad_top(*top)
+                .pad_right(*right)
+                .pad_bottom(*bottom)
+                .pad_left(*left),
+            Node::Fill(_, right, bottom, filler) => pop()
+                .fill_right(*right, *filler)
+                .fill_bottom(*bottom, *filler),
+            Node::Beside(_, _, align) => {
+                let (right, mut left) = (pop(), pop());
+                match align {
+                    VAlign::Top => {
+                        left.append_right(&right);
+                        left
+                    }
+                    _ => left.beside(&right, *align),
+                }
+            }
+            Node::Stack(_, _, align) => {
+                let (bottom, mut top) = (pop(), pop());
+                match align {
+                    HAlign::Left => {
+                        top.append_below(&bottom);
+                        top
+                    }
+                    _ => top.stack(&bottom, *align),
+                }
+            }
+            Node::Overlay(_, _) => {
+                let (back, front) = (pop(), pop());
+                front.in_front_of(&back)
+            }
+        }
+    }
+}
+
+/// A block recording how it is composed from other blocks, computing its
+/// lines only when needed, see [LazyBlock::to_block] and
+/// [LazyBlock::render].
+///
+/// Composing lazy blocks is cheap, since nothing is padded or joined until
+/// the lines are computed. The width and height are known without
+/// computing any lines. Clones share the composition tree.
+///
+/// ```
+/// use text_block_layout::{HAlign, LazyBlock, VAlign};
+///
+/// let fraction = LazyBlock::of("1")
+///     .stack(&LazyBlock::of("───"), HAlign::CenterLeft)
+///     .stack(&LazyBlock::of("x"), HAlign::CenterLeft);
+/// let expression = LazyBlock::of("y = ").beside(&fraction, VAlign::CenterTop);
+///
+/// assert_eq!((7, 3), (expression.width(), expression.height()));
+/// assert_eq!("     1\ny = ───\n     x", expression.render());
+/// ```
+#[derive(Clone, Debug)]
+pub struct LazyBlock {
+    node: Arc<Node>,
+    width: usize,
+    height: usize,
+}
+
+impl LazyBlock {
+    /// Create lazy block from a node of given size.
+    fn new(node: Node, width: usize, height: usize) -> Self {
+        LazyBlock {
+            node: Arc::new(node),
+            width,
+            height,
+        }
+    }
+
+    /// Create lazy block of a block, or anything convertible to a block like
+    /// text.
+    pub fn of<T: Into<Block>>(block: T) -> Self {
+        let block = block.into();
+        let (width, height) = (block.width(), block.height());
+        LazyBlock::new(Node::Text(block), width, height)
+    }
+
+    /// Create empty lazy block with width and height zero.
+    pub fn empty() -> Self {
+        LazyBlock::of(Block::empty())
+    }
+
+    /// Width of the block.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Height of the block.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Pad sides of block with spaces, in order top, right, bottom and left.
+    fn pad(&self, sides: [usize; 4]) -> Self {
+        let [top, right, bottom, left] = sides;
+        let width = self.width + left + right;
+        let height = self.height + top + bottom;
+        LazyBlock::new(Node::Pad(self.clone(), sides), width, height)
+    }
+
+    /// Pad top side of block with given number of empty lines.
+    pub fn pad_top(&self, height: usize) -> Self {
+        self.pad([height, 0, 0, 0])
+    }
+
+    /// Pad right side of block with given number of spaces.
+    pub fn pad_right(&self, width: usize) -> Self {
+        self.pad([0, width, 0, 0])
+    }
+
+    /// Pad bottom side of block with given number of empty lines.
+    pub fn pad_bottom(&self, height: usize) -> Self {
+        self.pad([0, 0, height, 0])
+    }
+
+    /// Pad left side of block with given number of spaces.
+    pub fn pad_left(&self, width: usize) -> Self {
+        self.pad([0, 0, 0, width])
+    }
+
+    /// Fill right side of block with given number of the filler character.
+    pub fn fill_right(&self, width: usize, filler: char) -> Self {
+        let node = Node::Fill(self.clone(), width, 0, filler);
+        LazyBlock::new(node, self.width + width, self.height)
+    }
+
+    /// Fill bottom side of block with given number of the filler character.
+    pub fn fill_bottom(&self, height: usize, filler: char) -> Self {
+        let node = Node::Fill(self.clone(), 0, height, filler);
+        LazyBlock::new(node, self.width, self.height + height)
+    }
+
+    /// Join two blocks horizontally, like [Block::beside].
+    pub fn beside(&self, right: &LazyBlock, align: VAlign) -> Self {
+        let node = Node::Beside(self.clone(), right.clone(), align);
+        let height = self.height.max(right.height);
+        LazyBlock::new(node, self.width + right.width, height)
+    }
+
+    /// Join two blocks vertically, like [Block::stack].
+    pub fn stack(&self, bottom: &LazyBlock, align: HAlign) -> Self {
+        let node = Node::Stack(self.clone(), bottom.clone(), align);
+        let width = self.width.max(bottom.width);
+        LazyBlock::new(node, width, self.height + bottom.height)
+    }
+
+    /// Overlay self in front of given block, like [Block::in_front_of].
+    pub fn in_front_of(&self, behind: &LazyBlock) -> Self {
+        let node = Node::Overlay(self.clone(), behind.clone());
+        let width = self.width.max(behind.width);
+        let height = self.height.max(behind.height);
+        LazyBlock::new(node, width, height)
+    }
+
+    /// Compute the lines of the block.
+    ///
+    /// The composition tree is walked with an explicit stack, so deeply
+    /// nested blocks do not overflow the call stack. Blocks used in several
+    /// places of the tree are computed only once.
+    pub fn to_block(&self) -> Block {
+        let mut pending = vec![(self, false)];
+        let mut computed = Vec::new();
+        let mut shared = HashMap::new();
+
+        while let Some((block, children_computed)) = pending.pop() {
+            let key = Arc::as_ptr(&block.node);
+            let is_shared = Arc::strong_count(&block.node) > 1;
+            if children_computed {
+                let result = block.node.compute(&mut computed);
+                if is_shared {
+                    shared.insert(key, result.clone());
+                }
+                computed.push(result);
+            } else if let Some(result) = shared.get(&key) {
+                computed.push(result.clone());
+            } else {
+                pending.push((block, true));
+                let children = block.node.children();
+                pending.extend(children.into_iter().rev().map(|child| (child, false)));
+            }
+        }
+        computed.pop().expect("block is computed")
+    }
+
+    /// Render a string from the block, like [Block::render].
+    pub fn render(&self) -> String {
+        self.to_block().render()
+    }
+}
+
+impl Drop for LazyBlock {
+    /// Take apart nodes that are not shared with other blocks one by one,
+    /// since dropping them recursively would overflow the call stack for
+    /// deeply nested blocks.
+    fn drop(&mut self) {
+        match Arc::get_mut(&mut self.node) {
+            None | Some(Node::Text(_)) => return,
+            Some(_) => {}
+        }
+
+        let placeholder = Arc::new(Node::Text(Block::empty()));
+        let mut nodes = vec![mem::replace(&mut self.node, placeholder.clone())];
+        while let Some(mut node) = nodes.pop() {
+            if let Some(node) = Arc::get_mut(&mut node) {
+                for child in node.children_mut() {
+                    nodes.push(mem::replace(&mut child.node, placeholder.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl Default for LazyBlock {
+    fn default() -> Self {
+        LazyBlock::empty()
+    }
+}
+
+impl From<Block> for LazyBlock {
+    fn from(block: Block) -> Self {
+        LazyBlock::of(block)
+    }
+}
+
+impl From<&LazyBlock> for Block {
+    fn from(block: &LazyBlock) -> Self {
+        block.to_block()
+    }
+}
+
+impl From<LazyBlock> for Block {
+    fn from(block: LazyBlock) -> Self {
+        block.to_block()
+    }
+}
+
+impl std::fmt::Display for LazyBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_block())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn size_without_computing_lines() {
+        let b = LazyBlock::of("abc")
+            .pad_left(2)
+            .pad_top(1)
+            .fill_bottom(2, '.')
+            .beside(&LazyBlock::of("x\ny"), VAlign::Top);
+
+        assert_eq!((6, 4), (b.width(), b.height()));
+        assert_eq!((6, 4), (b.to_block().width(), b.to_block().height()));
+    }
+
+    #[test]
+    fn same_result_as_block() {
+        let (a, b) = (Block::of("a\nbb"), Block::of("ccc"));
+        let (lazy_a, lazy_b) = (LazyBlock::of(&a), LazyBlock::of(&b));
+
+        assert_eq!(
+            a.pad_left(1).pad_top(2).pad_right(3).pad_bottom(4),
+            lazy_a
+                .pad_left(1)
+                .pad_top(2)
+                .pad_right(3)
+                .pad_bottom(4)
+                .to_block()
+        );
+        assert_eq!(
+            a.fill_right(2, '-').fill_bottom(1, '|'),
+            lazy_a.fill_right(2, '-').fill_bottom(1, '|').to_block()
+        );
+        for &align in &[VAlign::Top, VAlign::CenterBottom] {
+            assert_eq!(
+                a.beside(&b, align),
+                lazy_a.beside(&lazy_b, align).to_block()
+            );
+            assert_eq!(
+                b.beside(&a, align),
+                lazy_b.beside(&lazy_a, align).to_block()
+            );
+        }
+        for &align in &[HAlign::Left, HAlign::Right] {
+            assert_eq!(a.stack(&b, align), lazy_a.stack(&lazy_b, align).to_block());
+            assert_eq!(b.stack(&a, align), lazy_b.stack(&lazy_a, align).to_block());
+        }
+        assert_eq!(a.in_front_of(&b), lazy_a.in_front_of(&lazy_b).to_block());
+    }
+
+    #[test]
+    fn deep_composition() {
+        let line = LazyBlock::of("ab");
+        let b = (0..100_000).fold(LazyBlock::empty(), |b, _| b.stack(&line, HAlign::Left));
+
+        assert_eq!(100_000, b.height());
+        assert_eq!(
+            Block::stack_all(vec![Block::of("ab"); 100_000], HAlign::Left),
+            b.to_block()
+        );
+    }
+
+    #[test]
+    fn shared_blocks_computed_once() {
+        let b = (0..64).fold(LazyBlock::of("ab"), |b, _| b.in_front_of(&b));
+        let c = (0..16).fold(LazyBlock::of("a"), |c, _| c.stack(&c, HAlign::Left));
+
+        assert_eq!(Block::of("ab"), b.to_block());
+        assert_eq!(
+            Block::stack_all(vec![Block::of("a"); 1 << 16], HAlign::Left),
+            c.to_block()
+        );
+    }
+
+    #[test]
+    fn drop_deep_composition() {
+        let b = (0..1_000_000).fold(LazyBlock::of("a"), |b, _| b.pad_top(1));
+
+        assert_eq!(1_000_001, b.height());
+        drop(b);
+    }
+}
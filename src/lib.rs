@@ -1,4 +1,37 @@
-use unicode_width::UnicodeWidthStr;
+mod align;
+mod append;
+mod border;
+mod box_drawing;
+mod canvas;
+mod cell;
+mod crop;
+mod decimal;
+mod frame;
+mod lazy;
+mod lines;
+mod overlay;
+mod render;
+mod table;
+mod truncate;
+mod wrap;
+
+use cell::{covering, grapheme_width, line_cells};
+use lines::Lines;
+use std::borrow::Borrow;
+use unicode_segmentation::UnicodeSegmentation;
+
+pub use align::{HAlign, VAlign};
+pub use border::{BorderStyle, Stroke};
+pub use canvas::Canvas;
+pub use cell::Cell;
+pub use decimal::DecimalAlign;
+pub use frame::Frame;
+pub use lazy::LazyBlock;
+pub use overlay::Compositing;
+pub use render::{LineEnding, RenderOptions};
+pub use table::{Column, Table, TableCell};
+pub use truncate::EllipsisPosition;
+pub use wrap::TextAlign;
 
 /// Represents a block, i.e. a rectangle, of some width and height containing
 /// text.
@@ -7,10 +40,16 @@ use unicode_width::UnicodeWidthStr;
 /// how chunks of text should be positioned in relation to other block, by
 /// joining blocks together, either vertically or horizontally, and using
 /// paddings and fills.
+///
+/// The lines of a block are shared between clones, so cloning a block is
+/// cheap. Joining blocks with `stack_*` and `beside_*` shares the lines of
+/// the joined blocks as well, taking constant time, and padding and filling
+/// do not copy the lines. The joined lines are computed once, the first
+/// time they are read, for example when rendering.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Block {
     width: usize,
-    lines: Vec<String>,
+    lines: Lines,
 }
 
 /// Repeat a character a given ammount of times.
@@ -23,20 +62,85 @@ fn subtract_or_zero(a: usize, b: usize) -> usize {
     a.saturating_sub(b)
 }
 
-/// Join two blocks vertically, requiring blocks to have same width.
-fn stack_same_width(top: &Block, bottom: &Block) -> Block {
-    assert_eq!(top.width(), bottom.width());
+/// Display width of a single line of text, as the sum of the widths of its
+/// grapheme clusters. This is the number of cells the line is split into,
+/// see [Block::cells].
+fn line_width(line: &str) -> usize {
+    line.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Split text into lines on '\n', also removing any '\r' preceding the
+/// newline, so that both Unix and Windows line endings are handled.
+fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+}
+
+/// Create block from lines not containing any newlines, padding lines on the
+/// right side to the width of the widest line.
+fn of_single_lines(lines: Vec<String>) -> Block {
+    let width = lines.iter().map(|line| line_width(line)).max().unwrap_or(0);
+
+    let lines = lines
+        .into_iter()
+        .map(|line| {
+            let padding = subtract_or_zero(width, line_width(&line));
+            line + &repeat(' ', padding)
+        })
+        .collect::<Vec<String>>();
+
+    Block::new(width, lines)
+}
+
+/// Repeat the lines of block so that given height is reached. Block of
+/// height zero is padded with spaces instead.
+fn tile_to_height(block: &Block, height: usize) -> Block {
+    if block.height() == 0 {
+        return block.pad_bottom(height);
+    }
 
-    let lines = top
+    let lines = block
         .lines
         .iter()
+        .cycle()
+        .take(height)
         .cloned()
-        .chain(bottom.lines.iter().cloned())
         .collect::<Vec<String>>();
 
+    Block::new(block.width, lines)
+}
+
+/// Repeat the columns of block so that given width is reached. Block of
+/// width zero is padded with spaces instead.
+fn tile_to_width(block: &Block, width: usize) -> Block {
+    if block.width == 0 {
+        return block.pad_right(width);
+    }
+
+    let times = width.div_ceil(block.width);
+    let lines = block
+        .lines
+        .iter()
+        .map(|line| line.repeat(times))
+        .collect::<Vec<String>>();
+
+    Block::new(block.width * times, lines).truncate_to_width(width)
+}
+
+/// Join two blocks vertically, requiring blocks to have same width.
+fn stack_same_width(top: &Block, bottom: &Block) -> Block {
+    assert_eq!(top.width(), bottom.width());
+
+    // Share lines when there is nothing to join
+    if top.height() == 0 {
+        return bottom.clone();
+    } else if bottom.height() == 0 {
+        return top.clone();
+    }
+
     Block {
         width: top.width,
-        lines,
+        lines: top.lines.stack(&bottom.lines),
     }
 }
 
@@ -44,28 +148,39 @@ fn stack_same_width(top: &Block, bottom: &Block) -> Block {
 fn beside_same_height(left: &Block, right: &Block) -> Block {
     assert_eq!(left.height(), right.height());
 
-    let lines = left
-        .lines
-        .iter()
-        .zip(right.lines.iter())
-        .map(|a| a.0.to_string() + a.1)
-        .collect::<Vec<String>>();
+    // Share lines when there is nothing to join
+    if left.width == 0 {
+        return right.clone();
+    } else if right.width == 0 {
+        return left.clone();
+    }
 
     Block {
         width: left.width + right.width,
-        lines,
+        lines: left.lines.beside(&right.lines),
     }
 }
 
 impl Block {
-    /// Create empty block with width and height zero.
-    pub fn empty() -> Self {
+    /// Create block of given width from lines, all of that display width.
+    fn new(width: usize, lines: Vec<String>) -> Self {
         Block {
-            width: 0,
-            lines: vec![],
+            width,
+            lines: Lines::from(lines),
         }
     }
 
+    /// Return lines for modification, copying them first if they are shared
+    /// with other blocks.
+    fn lines_mut(&mut self) -> &mut Vec<String> {
+        self.lines.make_mut()
+    }
+
+    /// Create empty block with width and height zero.
+    pub fn empty() -> Self {
+        Block::new(0, vec![])
+    }
+
     /// Create block of given width and height 0.
     pub fn of_width(width: usize) -> Self {
         Block::empty().pad_right(width)
@@ -76,28 +191,105 @@ impl Block {
         Block::empty().pad_to_height_bottom(height)
     }
 
-    /// Create block of given text. Uses width of text and height 1.
+    /// Create block of given text. Text is split into lines on '\n' or
+    /// "\r\n". Uses width of the widest line and height of the number of
+    /// lines.
     fn of_string(text: String) -> Self {
-        let width = UnicodeWidthStr::width(text.as_str());
-        Block {
-            width,
-            lines: vec![text],
+        if text.contains('\n') {
+            Block::of_lines(split_lines(&text))
+        } else {
+            let width = line_width(&text);
+            Block::new(width, vec![text])
         }
     }
 
     /// Creates block of any argument implementing `std::string::ToString`
     /// trait, or implicitly by implementing `std::fmt::Display`. Uses
-    /// String representation in block. See `Block::of_string`.
+    /// String representation in block, with one block line per line in the
+    /// text. See `Block::of_string`.
     pub fn of<T: ToString>(t: T) -> Self {
         Block::of_string(t.to_string())
     }
 
+    /// Create block with the given lines stacked on top of each other,
+    /// aligned to the left. Any line containing newlines is split into
+    /// several lines. Width of block is the width of the widest line.
+    pub fn of_lines<I>(lines: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let lines = lines
+            .into_iter()
+            .flat_map(|line| {
+                split_lines(line.as_ref())
+                    .map(str::to_string)
+                    .collect::<Vec<String>>()
+            })
+            .collect::<Vec<String>>();
+
+        of_single_lines(lines)
+    }
+
     /// Create block containing given text. Gets width of the text and height 1.
     #[deprecated(since = "1.2.0", note = "please use `Block::of` instead")]
     pub fn of_text(text: &str) -> Self {
         Block::of(text)
     }
 
+    /// Return content of block as a grid of cells, one row per line and
+    /// one cell per display column. Each row has exactly the width of the
+    /// block. Wide grapheme clusters are followed by continuation cells.
+    pub fn cells(&self) -> Vec<Vec<Cell<'_>>> {
+        self.lines
+            .iter()
+            .map(|line| line_cells(line, self.width))
+            .collect()
+    }
+
+    /// Return cell at given display column and line, or nothing if outside
+    /// of block. The column after a wide grapheme cluster gives a
+    /// continuation cell. The line is only scanned up to the column, use
+    /// [Block::cells] to go through all cells.
+    pub fn cell_at(&self, x: usize, y: usize) -> Option<Cell<'_>> {
+        let line = self.lines.get(y).filter(|_| x < self.width)?;
+        let (bytes, columns) = covering(line, x, x + 1);
+        if columns.start < x {
+            return Some(Cell::Continuation);
+        }
+        Some(Cell::Grapheme {
+            text: &line[bytes],
+            width: columns.end - columns.start,
+        })
+    }
+
+    /// Return first character of the grapheme cluster covering given
+    /// display column and line, or nothing if outside of block. Both columns
+    /// of a wide character give that character. The line is only scanned up
+    /// to the column, like in [Block::cell_at].
+    pub fn char_at(&self, x: usize, y: usize) -> Option<char> {
+        let line = self.lines.get(y).filter(|_| x < self.width)?;
+        let (bytes, _) = covering(line, x, x + 1);
+        line[bytes].chars().next()
+    }
+
+    /// Return iterator over the lines of block. All lines have the width of
+    /// the block, including any spaces on the right side.
+    pub fn lines(&self) -> impl DoubleEndedIterator<Item = &str> + ExactSizeIterator {
+        self.lines.iter().map(String::as_str)
+    }
+
+    /// Return line of given index, or nothing if outside of block.
+    pub fn line(&self, y: usize) -> Option<&str> {
+        self.lines.get(y).map(String::as_str)
+    }
+
+    /// Convert block into its lines. All lines have the width of the block,
+    /// including any spaces on the right side.
+    pub fn into_lines(self) -> Vec<String> {
+        self.lines.into_vec()
+    }
+
     /// Return height of block.
     pub fn height(&self) -> usize {
         self.lines.len()
@@ -117,38 +309,31 @@ impl Block {
     /// Add given text lines at bottom of block, incrementing the height
     /// accordingly. Width of block will be increades if needed.
     pub fn add_multiple_texts(&self, texts: &[String]) -> Self {
-        let addition = texts
-            .iter()
-            .fold(Block::empty(), |acc, text| acc.add_text(text));
-
-        self.stack_left(&addition)
+        self.stack_left(&Block::of_lines(texts))
     }
 
     /// Fill right side of block with given number of the filler character.
     pub fn fill_right(&self, width: usize, filler: char) -> Self {
-        let suffix = repeat(filler, width);
-
-        let lines = self
-            .lines
-            .iter()
-            .map(|line| line.to_string() + &suffix)
-            .collect::<Vec<String>>();
-
-        Block {
-            width: self.width + width,
-            lines,
+        if width == 0 {
+            return self.clone();
         }
+        let filling = Block {
+            width,
+            lines: Lines::repeat(repeat(filler, width), self.height()),
+        };
+        beside_same_height(self, &filling)
     }
 
     /// Fill bottom side of block with given number of the filler character.
     pub fn fill_bottom(&self, height: usize, filler: char) -> Self {
-        let padding = repeat(filler, self.width);
-
-        let mut result = self.clone();
-        for _ in 0..height {
-            result.lines.push(padding.clone())
+        if height == 0 {
+            return self.clone();
         }
-        result
+        let filling = Block {
+            width: self.width,
+            lines: Lines::repeat(repeat(filler, self.width), height),
+        };
+        stack_same_width(self, &filling)
     }
 
     /// Pad right side of block with given number of spaces.
@@ -170,82 +355,186 @@ impl Block {
         self.fill_bottom(height, ' ')
     }
 
+    /// Pad so given width is reached, placing the block according to given
+    /// alignment. Wider block is untouched.
+    pub fn pad_to_width(&self, width: usize, align: HAlign) -> Self {
+        let (left, right) = align.split(subtract_or_zero(width, self.width));
+        let padded = self.pad_right(right);
+        if left == 0 {
+            padded
+        } else {
+            padded.pad_left(left)
+        }
+    }
+
+    /// Pad so given height is reached, placing the block according to given
+    /// alignment. Higher block is untouched.
+    pub fn pad_to_height(&self, height: usize, align: VAlign) -> Self {
+        let (top, bottom) = align.split(subtract_or_zero(height, self.height()));
+        let padded = self.pad_bottom(bottom);
+        if top == 0 {
+            padded
+        } else {
+            padded.pad_top(top)
+        }
+    }
+
     /// Pad right so given width is reached. Wider block is untouched.
     pub fn pad_to_width_right(&self, width: usize) -> Self {
-        self.pad_right(subtract_or_zero(width, self.width))
+        self.pad_to_width(width, HAlign::Left)
     }
 
     /// Pad left so given width is reached. Wider block is untouched.
     pub fn pad_to_width_left(&self, width: usize) -> Self {
-        self.pad_left(subtract_or_zero(width, self.width))
+        self.pad_to_width(width, HAlign::Right)
     }
 
     /// Pad both sides so given width is reached. Wider block is untouched.
     /// If padding needs to be uneven, there will be more padding on the
     /// right side.
     pub fn pad_to_width_center_right(&self, width: usize) -> Self {
-        let padding = subtract_or_zero(width, self.width);
-        let padding_left = padding / 2;
-        let padding_right = padding - padding_left;
-        self.pad_left(padding_left).pad_right(padding_right)
+        self.pad_to_width(width, HAlign::CenterLeft)
     }
 
     /// Pad both sides so given width is reached. Wider block is untouched.
     /// If padding needs to be uneven, there will be more padding on the
     /// left side.
     pub fn pad_to_width_center_left(&self, width: usize) -> Self {
-        let padding = subtract_or_zero(width, self.width);
-        let padding_right = padding / 2;
-        let padding_left = padding - padding_right;
-        self.pad_left(padding_left).pad_right(padding_right)
+        self.pad_to_width(width, HAlign::CenterRight)
     }
 
     /// Pad top so given height is reached. Higher block is untouched.
     pub fn pad_to_height_top(&self, height: usize) -> Self {
-        self.pad_top(subtract_or_zero(height, self.height()))
+        self.pad_to_height(height, VAlign::Bottom)
     }
 
     /// Pad bottom so given height is reached. Higher block is untouched.
     pub fn pad_to_height_bottom(&self, height: usize) -> Self {
-        self.pad_bottom(subtract_or_zero(height, self.height()))
+        self.pad_to_height(height, VAlign::Top)
     }
 
     /// Pad both top and bottom so given height is reached. Higher block is
     /// untouched. If padding needs to be uneven, there will be more padding
     /// on the top side.
     pub fn pad_to_height_center_top(&self, height: usize) -> Self {
-        let padding = subtract_or_zero(height, self.height());
-        let padding_bottom = padding / 2;
-        let padding_top = padding - padding_bottom;
-        self.pad_bottom(padding_bottom).pad_top(padding_top)
+        self.pad_to_height(height, VAlign::CenterBottom)
     }
 
     /// Pad both top and bottom so given height is reached. Higher block is
     /// untouched. If padding needs to be uneven, there will be more padding
     /// on the bottom side.
     pub fn pad_to_height_center_bottom(&self, height: usize) -> Self {
-        let padding = subtract_or_zero(height, self.height());
-        let padding_top = padding / 2;
-        let padding_bottom = padding - padding_top;
-        self.pad_bottom(padding_bottom).pad_top(padding_top)
+        self.pad_to_height(height, VAlign::CenterTop)
     }
 
     /// Join two blocks horizontally, self to the left and the given
-    /// block to the right, aligning the top side of the blocks.
-    pub fn beside_top(&self, right: &Block) -> Self {
+    /// block to the right, aligning the blocks according to given alignment.
+    pub fn beside(&self, right: &Block, align: VAlign) -> Self {
         beside_same_height(
-            &self.pad_to_height_bottom(right.height()),
-            &right.pad_to_height_bottom(self.height()),
+            &self.pad_to_height(right.height(), align),
+            &right.pad_to_height(self.height(), align),
         )
     }
 
+    /// Join two blocks vertically, self on the top and the given
+    /// block on the bottom, aligning the blocks according to given alignment.
+    pub fn stack(&self, bottom: &Block, align: HAlign) -> Self {
+        stack_same_width(
+            &self.pad_to_width(bottom.width, align),
+            &bottom.pad_to_width(self.width, align),
+        )
+    }
+
+    /// Join many blocks horizontally, from left to right, aligning the blocks
+    /// according to given alignment. The resulting size is computed once,
+    /// making this faster than joining blocks one at a time.
+    pub fn beside_all<I>(blocks: I, align: VAlign) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Block>,
+    {
+        Block::beside_all_separated(blocks, &Block::empty(), align)
+    }
+
+    /// Join many blocks horizontally, like [Block::beside_all], with given
+    /// separator between each pair of blocks. The separator is repeated
+    /// vertically to cover the full height, so for example `Block::of("│")`
+    /// draws a line between the blocks.
+    pub fn beside_all_separated<I>(blocks: I, separator: &Block, align: VAlign) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Block>,
+    {
+        let blocks = blocks.into_iter().collect::<Vec<_>>();
+        let height = blocks
+            .iter()
+            .map(|block| block.borrow().height())
+            .max()
+            .unwrap_or(0);
+        let separator = tile_to_height(separator, height);
+        let blocks = blocks
+            .iter()
+            .map(|block| block.borrow().pad_to_height(height, align))
+            .collect::<Vec<Block>>();
+
+        let mut result = Block::of_height(height);
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 {
+                result = beside_same_height(&result, &separator);
+            }
+            result = beside_same_height(&result, block);
+        }
+        result
+    }
+
+    /// Join many blocks vertically, from top to bottom, aligning the blocks
+    /// according to given alignment. The resulting size is computed once,
+    /// making this faster than joining blocks one at a time.
+    pub fn stack_all<I>(blocks: I, align: HAlign) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Block>,
+    {
+        Block::stack_all_separated(blocks, &Block::empty(), align)
+    }
+
+    /// Join many blocks vertically, like [Block::stack_all], with given
+    /// separator between each pair of blocks. The separator is repeated
+    /// horizontally to cover the full width, so for example `Block::of("─")`
+    /// draws a line between the blocks.
+    pub fn stack_all_separated<I>(blocks: I, separator: &Block, align: HAlign) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Block>,
+    {
+        let blocks = blocks.into_iter().collect::<Vec<_>>();
+        let width = blocks
+            .iter()
+            .map(|block| block.borrow().width)
+            .max()
+            .unwrap_or(0);
+        let separator = tile_to_width(separator, width);
+
+        let mut result = Block::of_width(width);
+        for (index, block) in blocks.iter().enumerate() {
+            if index > 0 {
+                result = stack_same_width(&result, &separator);
+            }
+            result = stack_same_width(&result, &block.borrow().pad_to_width(width, align));
+        }
+        result
+    }
+
+    /// Join two blocks horizontally, self to the left and the given
+    /// block to the right, aligning the top side of the blocks.
+    pub fn beside_top(&self, right: &Block) -> Self {
+        self.beside(right, VAlign::Top)
+    }
+
     /// Join two blocks horizontally, self to the left and the given
     /// block to the right, aligning the bottom side of the blocks.
     pub fn beside_bottom(&self, right: &Block) -> Self {
-        beside_same_height(
-            &self.pad_to_height_top(right.height()),
-            &right.pad_to_height_top(self.height()),
-        )
+        self.beside(right, VAlign::Bottom)
     }
 
     /// Join two blocks horizontally, self to the left and the given
@@ -253,10 +542,7 @@ impl Block {
     /// If padding needs to be uneven, there will be more padding on the
     /// top side.
     pub fn beside_center_bottom(&self, right: &Block) -> Self {
-        beside_same_height(
-            &self.pad_to_height_center_top(right.height()),
-            &right.pad_to_height_center_top(self.height()),
-        )
+        self.beside(right, VAlign::CenterBottom)
     }
 
     /// Join two blocks horizontally, self to the left and the given
@@ -264,28 +550,19 @@ impl Block {
     /// If padding needs to be uneven, there will be more padding on the
     /// bottom side.
     pub fn beside_center_top(&self, right: &Block) -> Self {
-        beside_same_height(
-            &self.pad_to_height_center_bottom(right.height()),
-            &right.pad_to_height_center_bottom(self.height()),
-        )
+        self.beside(right, VAlign::CenterTop)
     }
 
     /// Join two blocks vertically, self on the top and the given
     /// block on the bottom, aligning the right side of the blocks.
     pub fn stack_right(&self, bottom: &Block) -> Self {
-        stack_same_width(
-            &self.pad_to_width_left(bottom.width),
-            &bottom.pad_to_width_left(self.width),
-        )
+        self.stack(bottom, HAlign::Right)
     }
 
     /// Join two blocks vertically, self on the top and the given
     /// block on the bottom, aligning the left side of the blocks.
     pub fn stack_left(&self, bottom: &Block) -> Self {
-        stack_same_width(
-            &self.pad_to_width_right(bottom.width),
-            &bottom.pad_to_width_right(self.width),
-        )
+        self.stack(bottom, HAlign::Left)
     }
 
     /// Join two blocks vertically, self on the top and the given
@@ -293,10 +570,7 @@ impl Block {
     /// If padding needs to be uneven, there will be more padding on the
     /// right side.
     pub fn stack_center_left(&self, bottom: &Block) -> Self {
-        stack_same_width(
-            &self.pad_to_width_center_right(bottom.width),
-            &bottom.pad_to_width_center_right(self.width),
-        )
+        self.stack(bottom, HAlign::CenterLeft)
     }
 
     /// Join two blocks vertically, self on the top and the given
@@ -304,10 +578,7 @@ impl Block {
     /// If padding needs to be uneven, there will be more padding on the
     /// left side.
     pub fn stack_center_right(&self, bottom: &Block) -> Self {
-        stack_same_width(
-            &self.pad_to_width_center_left(bottom.width),
-            &bottom.pad_to_width_center_left(self.width),
-        )
+        self.stack(bottom, HAlign::CenterRight)
     }
 
     /// Overlays self in front of given block. Treats spaces as transparent
@@ -318,54 +589,41 @@ impl Block {
 
     /// Overlays self in front of given block, showing content of the block
     /// behind on the characters defined as transparent.
+    ///
+    /// Blocks are overlayed per display column, see [Block::cells], so wide
+    /// characters, like CJK characters and emojis, and grapheme clusters,
+    /// like characters with combining marks, are kept aligned. A wide
+    /// character in front hides everything behind it. A wide character
+    /// behind that is only partially hidden by a character in front is
+    /// replaced with spaces on its visible columns.
     pub fn in_front_of_with_transparency(&self, behind: &Block, transparency: char) -> Self {
-        // Making sure the blocks is of same size
-        let front = self
-            .fill_right(subtract_or_zero(behind.width(), self.width()), transparency)
-            .fill_bottom(
-                subtract_or_zero(behind.height(), self.height()),
-                transparency,
-            );
-
-        let back = behind
-            .pad_to_width_right(self.width)
-            .pad_to_height_bottom(self.height());
-
-        // Zip characters and make sure frontmost is shown if not transparent
-        let lines = front
-            .lines
-            .iter()
-            .zip(back.lines.iter())
-            .map(|(front_line, back_line)| {
-                front_line
-                    .chars()
-                    .zip(back_line.chars())
-                    .map(|(front_char, back_char)| {
-                        if front_char == transparency {
-                            back_char
-                        } else {
-                            front_char
-                        }
-                    })
-                    .collect::<String>()
-            })
-            .collect::<Vec<String>>();
+        self.in_front_of_with(behind, &Compositing::transparent(&transparency.to_string()))
+    }
 
-        Block {
-            width: front.width,
-            lines,
-        }
+    /// Overlays self in front of given block, like [Block::in_front_of],
+    /// merging box-drawing characters in front with box-drawing characters
+    /// behind into the matching junctions. For example `─` in front of `│`
+    /// becomes `┼`, `┐` in front of `┌` becomes `┬` and `─` in front of `║`
+    /// becomes `╫`. Where both characters have a line in the same direction,
+    /// the line in front is used.
+    pub fn in_front_of_merging_lines(&self, behind: &Block) -> Self {
+        self.in_front_of_with(behind, &Compositing::merge_lines())
+    }
+
+    /// Overlays self in front of given block, choosing which block to show
+    /// in each display column according to given compositing rule.
+    pub fn in_front_of_with(&self, behind: &Block, compositing: &Compositing) -> Self {
+        behind.overlay_at_with(self, 0, 0, compositing)
     }
 
     /// Render a string from a block using '\n' as separator between lines.
     /// Trims away whitespace on the right side of each line, just to save on
     /// final string length.
     pub fn render(&self) -> String {
-        self.lines
-            .iter()
-            .map(|line| line.trim_end())
-            .collect::<Vec<_>>()
-            .join("\n")
+        let mut text = String::with_capacity(self.lines.iter().map(|line| line.len() + 1).sum());
+        self.render_into(&mut text)
+            .expect("writing to a string never fails");
+        text
     }
 }
 
@@ -387,9 +645,41 @@ impl From<String> for Block {
     }
 }
 
+impl From<&Block> for Block {
+    fn from(block: &Block) -> Self {
+        block.clone()
+    }
+}
+
+/// Collects blocks, or anything convertible to blocks like text, by stacking
+/// them on top of each other, aligning the left side of the blocks.
+impl<T: Into<Block>> std::iter::FromIterator<T> for Block {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Block::stack_all(iter.into_iter().map(Into::into), HAlign::Left)
+    }
+}
+
+/// Extends block by stacking blocks, or anything convertible to blocks like
+/// text, at the bottom of the block, aligning the left side of the blocks.
+impl<T: Into<Block>> Extend<T> for Block {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            self.append_below(&item.into());
+        }
+    }
+}
+
+/// Sums blocks, or anything convertible to blocks like text, by stacking them
+/// on top of each other, aligning the left side of the blocks.
+impl<T: Into<Block>> std::iter::Sum<T> for Block {
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.collect()
+    }
+}
+
 impl std::fmt::Display for Block {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.render())
+        self.render_into(f)
     }
 }
 
@@ -422,6 +712,303 @@ mod test {
         assert_eq!(" a a\nbbbbb\nc\n\n", b.render());
     }
 
+    #[test]
+    fn of_text_with_newlines() {
+        let b = Block::of("a\nbbb\r\ncc");
+
+        assert_eq!(3, b.height());
+        assert_eq!(3, b.width());
+        assert_eq!("a\nbbb\ncc", b.render());
+        assert_eq!(b, Block::of("a").add_text("bbb").add_text("cc"));
+    }
+
+    #[test]
+    fn of_text_with_trailing_newline() {
+        let b = Block::of("a\n");
+
+        assert_eq!(2, b.height());
+        assert_eq!(1, b.width());
+        assert_eq!(1, Block::of("").height());
+    }
+
+    #[test]
+    fn of_lines() {
+        assert_eq!(Block::empty(), Block::of_lines(Vec::<String>::new()));
+
+        let b = Block::of_lines(["aa", "b\nc", "世界"]);
+
+        assert_eq!(4, b.height());
+        assert_eq!(4, b.width());
+        assert_eq!("aa\nb\nc\n世界", b.render());
+    }
+
+    #[test]
+    fn in_front_of_wide_characters() {
+        let front = Block::of("世 界");
+        let back = Block::of("abcde");
+
+        assert_eq!("世c界", front.in_front_of(&back).render());
+        assert_eq!("ab世e", Block::of("  世").in_front_of(&back).render());
+    }
+
+    #[test]
+    fn in_front_of_partially_hidden_wide_character() {
+        let back = Block::of("世界");
+
+        assert_eq!("x 界", Block::of("x").in_front_of(&back).render());
+        assert_eq!(" x界", Block::of(" x").in_front_of(&back).render());
+        assert_eq!(" 世", Block::of(" 世").in_front_of(&back).render());
+    }
+
+    #[test]
+    fn in_front_of_combining_characters() {
+        let front = Block::of(" e\u{301}");
+        let back = Block::of("abc");
+
+        assert_eq!("ae\u{301}c", front.in_front_of(&back).render());
+        assert_eq!(3, front.in_front_of(&back).width());
+    }
+
+    #[test]
+    fn in_front_of_grapheme_clusters() {
+        let flag = "🇸🇪";
+        let front = Block::of(format!(" {}", flag));
+        let back = Block::of("abcd");
+
+        assert_eq!(format!("a{}d", flag), front.in_front_of(&back).render());
+    }
+
+    #[test]
+    fn in_front_of_merging_lines() {
+        let front = Block::of("┌─┐\n│ │\n└─┘");
+        let back = front.pad_left(2).pad_top(1);
+
+        assert_eq!(
+            "┌─┐\n│ ├─┐\n└─┤ │\n  └─┘",
+            front.in_front_of_merging_lines(&back).render()
+        );
+        assert_eq!(
+            "╫x",
+            Block::of("─x")
+                .in_front_of_merging_lines(&Block::of("║│"))
+                .render()
+        );
+    }
+
+    #[test]
+    fn lines_are_shared() {
+        let a = Block::of("abc\ndef");
+
+        assert!(a.lines.ptr_eq(&a.clone().lines));
+        assert!(a.lines.ptr_eq(&a.pad_to_width_right(2).lines));
+        assert!(a.lines.ptr_eq(&a.stack_left(&Block::empty()).lines));
+        assert!(a.lines.ptr_eq(&Block::of_height(2).beside_top(&a).lines));
+    }
+
+    #[test]
+    fn joined_blocks_share_lines() {
+        let a = Block::of("abc\ndef");
+        let b = a.stack_left(&a).beside_center_top(&a).pad_left(1);
+
+        assert_eq!(Block::of(" abc\n defabc\n abcdef\n def"), b);
+        assert_eq!(
+            Block::of("abc\ndef\nabc\ndef"),
+            Block::stack_all(vec![&a, &a], HAlign::Left)
+        );
+        assert_eq!(vec!["abc", "def"], *a.lines);
+    }
+
+    #[test]
+    fn stack_in_loop_takes_linear_time() {
+        let line = Block::of("ab");
+        let mut b = Block::empty();
+        for _ in 0..100_000 {
+            b = b.stack_left(&line);
+        }
+        let mut c = Block::empty();
+        for _ in 0..100_000 {
+            c = c.beside_top(&line);
+        }
+
+        assert_eq!(100_000, b.height());
+        assert_eq!(vec!["ab"; 100_000], b.into_lines());
+        assert_eq!("ab".repeat(100_000), c.render());
+    }
+
+    #[test]
+    fn access_lines() {
+        let b = Block::of("ab\nc");
+
+        assert_eq!(vec!["ab", "c "], b.lines().collect::<Vec<&str>>());
+        assert_eq!(2, b.lines().len());
+        assert_eq!(Some("c "), b.line(1));
+        assert_eq!(None, b.line(2));
+        assert_eq!(vec!["ab", "c "], b.clone().into_lines());
+        assert_eq!(vec!["ab", "c "], b.stack_left(&b).crop_top(2).into_lines());
+    }
+
+    #[test]
+    fn access_cells_and_characters() {
+        let b = Block::of("a世\ne\u{301}");
+
+        assert_eq!(Some('a'), b.char_at(0, 0));
+        assert_eq!(Some('世'), b.char_at(1, 0));
+        assert_eq!(Some('世'), b.char_at(2, 0));
+        assert_eq!(Some('e'), b.char_at(0, 1));
+        assert_eq!(Some(' '), b.char_at(1, 1));
+        assert_eq!(None, b.char_at(3, 0));
+        assert_eq!(None, b.char_at(0, 2));
+
+        assert_eq!(Some(2), b.cell_at(1, 0).map(|cell| cell.width()));
+        assert!(b.cell_at(2, 0).unwrap().is_continuation());
+        assert_eq!(Some("e\u{301}"), b.cell_at(0, 1).map(|cell| cell.text()));
+        assert!(b.cell_at(0, 2).is_none());
+        for (y, row) in b.cells().iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                assert_eq!(Some(*cell), b.cell_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn width_is_sum_of_grapheme_widths() {
+        let b = Block::of("لا");
+
+        assert_eq!(2, b.width());
+        assert_eq!(
+            vec!["ل", "ا"],
+            b.cells()[0].iter().map(Cell::text).collect::<Vec<_>>()
+        );
+        assert_eq!("لاxxxxxx", b.in_front_of(&Block::of("xxxxxxxx")).render());
+    }
+
+    #[test]
+    fn cells_of_block() {
+        let block = Block::of("世").add_text("ab");
+        let cells = block.cells();
+
+        assert_eq!(2, cells.len());
+        assert_eq!(
+            vec!["世", ""],
+            cells[0].iter().map(Cell::text).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["a", "b"],
+            cells[1].iter().map(Cell::text).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn stack_with_alignment() {
+        let a = Block::of("aaaa");
+        let b = Block::of("b");
+
+        assert_eq!(a.stack_left(&b), a.stack(&b, HAlign::Left));
+        assert_eq!(a.stack_right(&b), a.stack(&b, HAlign::Right));
+        assert_eq!("aaaa\n b", a.stack(&b, HAlign::CenterLeft).render());
+        assert_eq!("aaaa\n  b", a.stack(&b, HAlign::CenterRight).render());
+    }
+
+    #[test]
+    fn beside_with_alignment() {
+        let a = Block::of("a").add_text("a").add_text("a").add_text("a");
+        let b = Block::of("b");
+
+        assert_eq!(a.beside_top(&b), a.beside(&b, VAlign::Top));
+        assert_eq!(a.beside_bottom(&b), a.beside(&b, VAlign::Bottom));
+        assert_eq!("a\nab\na\na", a.beside(&b, VAlign::CenterTop).render());
+        assert_eq!("a\na\nab\na", a.beside(&b, VAlign::CenterBottom).render());
+    }
+
+    #[test]
+    fn pad_with_alignment() {
+        let b = Block::of("b");
+
+        assert_eq!(vec![" b  "], *b.pad_to_width(4, HAlign::CenterLeft).lines);
+        assert_eq!(vec!["  b "], *b.pad_to_width(4, HAlign::CenterRight).lines);
+        assert_eq!(
+            vec![" ", "b", " ", " "],
+            *b.pad_to_height(4, VAlign::CenterTop).lines
+        );
+        assert_eq!(
+            vec![" ", " ", "b", " "],
+            *b.pad_to_height(4, VAlign::CenterBottom).lines
+        );
+    }
+
+    #[test]
+    fn stack_all_blocks() {
+        let blocks = vec![Block::of("a"), Block::of("bbb"), Block::of("cc")];
+
+        let expected = blocks[0].stack_right(&blocks[1]).stack_right(&blocks[2]);
+        assert_eq!(expected, Block::stack_all(&blocks, HAlign::Right));
+        assert_eq!(
+            Block::empty(),
+            Block::stack_all(Vec::<Block>::new(), HAlign::Left)
+        );
+    }
+
+    #[test]
+    fn stack_all_separated_blocks() {
+        let blocks = vec![Block::of("a"), Block::of("bbbb")];
+        let separator = Block::of("-=");
+
+        assert_eq!(
+            "a\n-=-=\nbbbb",
+            Block::stack_all_separated(blocks, &separator, HAlign::Left).render()
+        );
+    }
+
+    #[test]
+    fn beside_all_blocks() {
+        let blocks = vec![Block::of("a"), Block::of("b\nb\nb"), Block::of("c")];
+
+        let expected = blocks[0]
+            .beside_center_top(&blocks[1])
+            .beside_center_top(&blocks[2]);
+        assert_eq!(expected, Block::beside_all(&blocks, VAlign::CenterTop));
+    }
+
+    #[test]
+    fn beside_all_separated_blocks() {
+        let blocks = vec![Block::of("a\na"), Block::of("b"), Block::of("c")];
+        let spacer = Block::of_width(1);
+
+        assert_eq!(
+            "a│b│c\na│ │",
+            Block::beside_all_separated(&blocks, &Block::of("│"), VAlign::Top).render()
+        );
+        assert_eq!(
+            "a b c\na",
+            Block::beside_all_separated(&blocks, &spacer, VAlign::Top).render()
+        );
+    }
+
+    #[test]
+    fn collect_blocks() {
+        let a = Block::of("a");
+        let b = Block::of("bbb");
+
+        assert_eq!(a.stack_left(&b), vec![&a, &b].into_iter().collect());
+        assert_eq!(
+            a.stack_left(&b),
+            vec![a.clone(), b.clone()].into_iter().sum()
+        );
+        assert_eq!(a.stack_left(&b), vec!["a", "bbb"].into_iter().collect());
+        assert_eq!(Block::of("a\nb"), "ab".chars().collect());
+    }
+
+    #[test]
+    fn extend_block() {
+        let mut block = Block::of("aa");
+        block.extend(["b", "c"]);
+        block.extend(vec!["dd\nd".to_string()]);
+        block.extend(std::iter::once(Block::of("eee")));
+
+        assert_eq!("aa\nb\nc\ndd\nd\neee", block.render());
+        assert_eq!(3, block.width());
+    }
+
     #[test]
     fn from_numbers() {
         assert_eq!("2.56", Block::of(2.56_f64).to_string());
@@ -470,7 +1057,7 @@ mod monkey_test {
 
         gens::vec::any(lines).of_size(..100).map(
             |lines| Block::empty().add_multiple_texts(&lines),
-            |block| block.lines,
+            |block| block.lines.to_vec(),
         )
     }
 
@@ -0,0 +1,272 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+
+/// How the lines of a [Lines] are made up.
+enum Parts {
+    /// Lines stored as they are.
+    Leaf(Vec<String>),
+    /// The same line repeated given number of times.
+    Repeat(String, usize),
+    /// Lines of the first part followed by the lines of the second part,
+    /// with the total number of lines.
+    Stack(Lines, Lines, usize),
+    /// Each line of the first part joined with the same line of the second
+    /// part, with the number of lines of both parts.
+    Beside(Lines, Lines, usize),
+}
+
+struct Node {
+    parts: Parts,
+    /// Lines computed from the parts, the first time they are needed.
+    joined: OnceLock<Vec<String>>,
+}
+
+/// Lines of a [Block](crate::Block), shared between clones and between
+/// blocks joined from each other.
+///
+/// Joining lines vertically or horizontally only records the parts being
+/// joined, so it takes constant time. The joined lines are computed the
+/// first time they are read, copying each line of the parts once, and are
+/// kept for later reads.
+pub(crate) struct Lines {
+    node: Arc<Node>,
+}
+
+impl Lines {
+    fn new(parts: Parts) -> Self {
+        Lines {
+            node: Arc::new(Node {
+                parts,
+                joined: OnceLock::new(),
+            }),
+        }
+    }
+
+    /// Create lines consisting of given line repeated given number of times.
+    pub(crate) fn repeat(line: String, times: usize) -> Self {
+        Lines::new(Parts::Repeat(line, times))
+    }
+
+    /// Join lines vertically, self on the top.
+    pub(crate) fn stack(&self, bottom: &Lines) -> Self {
+        let len = self.len() + bottom.len();
+        Lines::new(Parts::Stack(self.clone(), bottom.clone(), len))
+    }
+
+    /// Join lines horizontally, self to the left. Both must have the same
+    /// number of lines.
+    pub(crate) fn beside(&self, right: &Lines) -> Self {
+        assert_eq!(self.len(), right.len());
+        Lines::new(Parts::Beside(self.clone(), right.clone(), self.len()))
+    }
+
+    /// Number of lines, known without computing any joined lines.
+    pub(crate) fn len(&self) -> usize {
+        match &self.node.parts {
+            Parts::Leaf(lines) => lines.len(),
+            Parts::Repeat(_, len) | Parts::Stack(_, _, len) | Parts::Beside(_, _, len) => *len,
+        }
+    }
+
+    /// Return true if there are no lines.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return true if both share the same lines.
+    #[cfg(test)]
+    pub(crate) fn ptr_eq(&self, other: &Lines) -> bool {
+        Arc::ptr_eq(&self.node, &other.node)
+    }
+
+    /// Lines if they are stored or already computed.
+    fn computed(&self) -> Option<&Vec<String>> {
+        match &self.node.parts {
+            Parts::Leaf(lines) => Some(lines),
+            _ => self.node.joined.get(),
+        }
+    }
+
+    /// Compute the lines from the parts. The parts are walked with an
+    /// explicit stack, appending to each line in order from left to right,
+    /// so deeply nested parts do not overflow the call stack.
+    fn join(&self) -> Vec<String> {
+        let mut result = vec![String::new(); self.len()];
+        let mut pending = vec![(self, 0)];
+
+        while let Some((lines, first)) = pending.pop() {
+            let result = &mut result[first..first + lines.len()];
+            if let Some(computed) = lines.computed() {
+                for (line, part) in result.iter_mut().zip(computed) {
+                    line.push_str(part);
+                }
+                continue;
+            }
+            match &lines.node.parts {
+                Parts::Leaf(_) => unreachable!("stored lines are computed"),
+                Parts::Repeat(part, _) => {
+                    for line in result {
+                        line.push_str(part);
+                    }
+                }
+                Parts::Stack(top, bottom, _) => {
+                    pending.push((bottom, first + top.len()));
+                    pending.push((top, first));
+                }
+                Parts::Beside(left, right, _) => {
+                    pending.push((right, first));
+                    pending.push((left, first));
+                }
+            }
+        }
+        result
+    }
+
+    /// Return lines for modification if they are stored and not shared
+    /// with other lines.
+    pub(crate) fn get_mut(&mut self) -> Option<&mut Vec<String>> {
+        match Arc::get_mut(&mut self.node).map(|node| &mut node.parts) {
+            Some(Parts::Leaf(lines)) => Some(lines),
+            _ => None,
+        }
+    }
+
+    /// Return lines for modification, copying them first if they are shared
+    /// with other lines.
+    pub(crate) fn make_mut(&mut self) -> &mut Vec<String> {
+        if self.get_mut().is_none() {
+            let lines = Arc::get_mut(&mut self.node)
+                .and_then(|node| node.joined.take())
+                .unwrap_or_else(|| self.to_vec());
+            *self = Lines::from(lines);
+        }
+        self.get_mut().expect("lines are stored and not shared")
+    }
+
+    /// Convert into lines, copying them if they are shared.
+    pub(crate) fn into_vec(mut self) -> Vec<String> {
+        mem::take(self.make_mut())
+    }
+}
+
+impl From<Vec<String>> for Lines {
+    fn from(lines: Vec<String>) -> Self {
+        Lines::new(Parts::Leaf(lines))
+    }
+}
+
+impl Deref for Lines {
+    type Target = Vec<String>;
+
+    fn deref(&self) -> &Vec<String> {
+        match &self.node.parts {
+            Parts::Leaf(lines) => lines,
+            _ => self.node.joined.get_or_init(|| self.join()),
+        }
+    }
+}
+
+impl Clone for Lines {
+    fn clone(&self) -> Self {
+        Lines {
+            node: self.node.clone(),
+        }
+    }
+}
+
+impl Drop for Lines {
+    /// Take apart joined parts that are not shared with other lines one by
+    /// one, since dropping them recursively would overflow the call stack
+    /// for deeply nested parts.
+    fn drop(&mut self) {
+        match Arc::get_mut(&mut self.node).map(|node| &node.parts) {
+            Some(Parts::Stack(..)) | Some(Parts::Beside(..)) => {}
+            _ => return,
+        }
+
+        let placeholder = Lines::from(vec![]);
+        let mut nodes = vec![mem::replace(&mut self.node, placeholder.node.clone())];
+        while let Some(mut node) = nodes.pop() {
+            if let Some(node) = Arc::get_mut(&mut node) {
+                if let Parts::Stack(first, second, _) | Parts::Beside(first, second, _) =
+                    &mut node.parts
+                {
+                    nodes.push(mem::replace(&mut first.node, placeholder.node.clone()));
+                    nodes.push(mem::replace(&mut second.node, placeholder.node.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl PartialEq for Lines {
+    fn eq(&self, other: &Lines) -> bool {
+        Arc::ptr_eq(&self.node, &other.node) || **self == **other
+    }
+}
+
+impl Eq for Lines {}
+
+impl Hash for Lines {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (**self).hash(state)
+    }
+}
+
+impl fmt::Debug for Lines {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Lines {
+        Lines::from(
+            lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn join_parts() {
+        let a = lines(&["a", "b"]);
+        let c = lines(&["c"]);
+        let joined = a
+            .beside(&Lines::repeat("-".to_string(), 2))
+            .stack(&c.beside(&c).beside(&c));
+
+        assert_eq!(3, joined.len());
+        assert_eq!(vec!["a-", "b-", "ccc"], *joined);
+        assert_eq!(lines(&["a-", "b-", "ccc"]), joined);
+    }
+
+    #[test]
+    fn modify_shared_lines() {
+        let a = lines(&["a"]);
+        let mut b = a.stack(&a);
+        b.make_mut().push("b".to_string());
+
+        assert_eq!(vec!["a"], *a);
+        assert_eq!(vec!["a", "a", "b"], b.clone().into_vec());
+    }
+
+    #[test]
+    fn deeply_nested_parts() {
+        let line = lines(&["ab"]);
+        let stacked = (0..1_000_000).fold(lines(&[]), |lines, _| lines.stack(&line));
+        let column = (0..1_000_000).fold(lines(&[""]), |lines, _| lines.beside(&line));
+
+        assert_eq!(1_000_000, stacked.len());
+        assert_eq!(2_000_000, column[0].len());
+        drop(stacked);
+        drop(column);
+    }
+}
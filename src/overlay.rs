@@ -0,0 +1,410 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::box_drawing;
+use crate::cell::{covering, grapheme_starts, line_cells, Cell};
+use crate::{repeat, Block};
+
+/// Rule deciding whether the block in front or the block behind is shown in
+/// each display column when overlaying blocks, see [Block::in_front_of_with]
+/// and [Block::overlay_at_with].
+///
+/// Columns outside the block in front always show the block behind. A wide
+/// character in front is shown or hidden as a whole. A wide character behind
+/// that is only partially hidden is replaced with spaces on its visible
+/// columns.
+#[derive(Clone)]
+pub struct Compositing {
+    mode: Mode,
+}
+
+#[derive(Clone)]
+enum Mode {
+    Transparent(Arc<dyn Fn(char) -> bool + Send + Sync>),
+    Underlay,
+    Opaque,
+    Mask(Block),
+    MergeLines,
+}
+
+impl Compositing {
+    /// Show block behind where the character in front is any of given
+    /// characters.
+    pub fn transparent(characters: &str) -> Self {
+        let characters = characters.chars().collect::<Vec<char>>();
+        Compositing::transparent_if(move |c| characters.contains(&c))
+    }
+
+    /// Show block behind where the character in front fulfills given
+    /// predicate. Grapheme clusters of several characters, like characters
+    /// with combining marks, are never transparent.
+    pub fn transparent_if<F>(predicate: F) -> Self
+    where
+        F: Fn(char) -> bool + Send + Sync + 'static,
+    {
+        Compositing {
+            mode: Mode::Transparent(Arc::new(predicate)),
+        }
+    }
+
+    /// Show block in front only where the block behind has spaces, so that
+    /// nothing behind is overwritten.
+    pub fn underlay() -> Self {
+        Compositing {
+            mode: Mode::Underlay,
+        }
+    }
+
+    /// Always show block in front, including its spaces.
+    pub fn opaque() -> Self {
+        Compositing { mode: Mode::Opaque }
+    }
+
+    /// Show block in front where given mask, aligned with the top left
+    /// corner of the block in front, has any other character than space.
+    /// Show block behind elsewhere, including outside of the mask.
+    pub fn mask(mask: &Block) -> Self {
+        Compositing {
+            mode: Mode::Mask(mask.clone()),
+        }
+    }
+
+    /// Treat spaces as transparent and merge box-drawing characters in
+    /// front with box-drawing characters behind, see
+    /// [Block::in_front_of_merging_lines].
+    pub fn merge_lines() -> Self {
+        Compositing {
+            mode: Mode::MergeLines,
+        }
+    }
+
+    /// Return cells of given line of the mask, if any.
+    fn mask_line(&self, line: usize) -> Vec<Cell<'_>> {
+        match &self.mode {
+            Mode::Mask(mask) => match mask.lines.get(line) {
+                Some(line) => line_cells(line, mask.width),
+                None => vec![],
+            },
+            _ => vec![],
+        }
+    }
+
+    /// Tell if grapheme cluster in front starting at given column is shown,
+    /// given the cell of the mask at the same position.
+    fn shows_front(
+        &self,
+        front: &[Cell],
+        back: &[Cell],
+        start: usize,
+        mask: Option<&Cell>,
+    ) -> bool {
+        let cell = front[start];
+        match &self.mode {
+            Mode::Transparent(is_transparent) => {
+                let mut chars = cell.text().chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => !is_transparent(c),
+                    _ => true,
+                }
+            }
+            Mode::Underlay => back[start..start + cell.width()]
+                .iter()
+                .all(|cell| cell.text() == " "),
+            Mode::Opaque => true,
+            Mode::Mask(_) => matches!(mask, Some(cell) if cell.text() != " "),
+            Mode::MergeLines => cell.text() != " ",
+        }
+    }
+
+    /// Overlay a single line in front of another line of given width. The
+    /// line in front starts at given offset and has given width. The cells of
+    /// the mask line are aligned with the line in front.
+    fn overlay_line(
+        &self,
+        front: &str,
+        back: &str,
+        offset: usize,
+        front_width: usize,
+        width: usize,
+        mask: &[Cell],
+    ) -> String {
+        let front = repeat(' ', offset) + front;
+        let front = line_cells(&front, width);
+        let back = line_cells(back, width);
+        let front_starts = grapheme_starts(&front);
+        let back_starts = grapheme_starts(&back);
+        let covered = offset..offset + front_width;
+
+        // Choose layer for each column, true for front and false for back
+        let layers = front_starts
+            .iter()
+            .map(|&start| {
+                covered.contains(&start)
+                    && self.shows_front(&front, &back, start, mask.get(start - offset))
+            })
+            .collect::<Vec<bool>>();
+
+        let mut result = String::new();
+        for (x, &is_front) in layers.iter().enumerate() {
+            let (cells, start) = if is_front {
+                (&front, front_starts[x])
+            } else {
+                (&back, back_starts[x])
+            };
+            let cell = cells[start];
+            let fully_visible = layers[start..start + cell.width()]
+                .iter()
+                .all(|&layer| layer == is_front);
+
+            if !fully_visible {
+                result.push(' ');
+            } else if start == x {
+                let merge = matches!(self.mode, Mode::MergeLines) && is_front;
+                match merged_line(cell, back[x]).filter(|_| merge) {
+                    Some(merged) => result.push(merged),
+                    None => result.push_str(cell.text()),
+                }
+            }
+        }
+        result
+    }
+}
+
+impl Default for Compositing {
+    /// Spaces are transparent, like in [Block::in_front_of].
+    fn default() -> Self {
+        Compositing::transparent(" ")
+    }
+}
+
+impl fmt::Debug for Compositing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.mode {
+            Mode::Transparent(_) => f.write_str("Transparent"),
+            Mode::Underlay => f.write_str("Underlay"),
+            Mode::Opaque => f.write_str("Opaque"),
+            Mode::Mask(mask) => f.debug_tuple("Mask").field(mask).finish(),
+            Mode::MergeLines => f.write_str("MergeLines"),
+        }
+    }
+}
+
+/// Merge cell in front with cell behind, if both are box-drawing characters.
+fn merged_line(front: Cell, behind: Cell) -> Option<char> {
+    let single_char = |cell: Cell| {
+        let mut chars = cell.text().chars();
+        chars.next().filter(|_| chars.next().is_none())
+    };
+    box_drawing::merge(single_char(front)?, single_char(behind)?)
+}
+
+/// Split signed offset into the part of the block clipped away and the
+/// position of the rest of it.
+fn clip(offset: isize) -> (usize, usize) {
+    if offset < 0 {
+        (offset.unsigned_abs(), 0)
+    } else {
+        (0, offset as usize)
+    }
+}
+
+impl Block {
+    /// Overlay given block in front of self, with its top left corner at
+    /// column `x` and line `y`, treating spaces as transparent like
+    /// [Block::in_front_of]. The result is grown as needed to fit the block
+    /// in front. Parts of the block in front at negative offsets are clipped
+    /// away, and a block clipped away completely leaves self unchanged.
+    ///
+    /// Only the columns covered by the block in front are recomposed. The
+    /// rest of the lines are kept as they are.
+    pub fn overlay_at(&self, front: &Block, x: isize, y: isize) -> Self {
+        self.overlay_at_with(front, x, y, &Compositing::default())
+    }
+
+    /// Overlay given block in front of self at given position, like
+    /// [Block::overlay_at], choosing which block to show in each display
+    /// column according to given compositing rule.
+    pub fn overlay_at_with(
+        &self,
+        front: &Block,
+        x: isize,
+        y: isize,
+        compositing: &Compositing,
+    ) -> Self {
+        let mut block = self.clone();
+        block.place_with(front, x, y, compositing);
+        block
+    }
+
+    /// Overlay given block in front of self at given position, in place,
+    /// like [Block::overlay_at_with].
+    pub(crate) fn place_with(
+        &mut self,
+        front: &Block,
+        x: isize,
+        y: isize,
+        compositing: &Compositing,
+    ) {
+        let (clip_x, x) = clip(x);
+        let (clip_y, y) = clip(y);
+        let front = front.crop(clip_x, clip_y, front.width, front.height());
+        if front.width == 0 || front.height() == 0 {
+            return;
+        }
+
+        self.widen(x + front.width);
+        let height = self.height().max(y + front.height());
+        let empty = repeat(' ', self.width);
+        self.lines_mut().resize(height, empty);
+
+        for (index, front_line) in front.lines.iter().enumerate() {
+            let mask = compositing.mask_line(clip_y + index);
+            let mask = mask.get(clip_x..).unwrap_or(&[]);
+
+            let line = &mut self.lines_mut()[y + index];
+            let (bytes, columns) = covering(line, x, x + front.width);
+            let composed = compositing.overlay_line(
+                front_line,
+                &line[bytes.clone()],
+                x - columns.start,
+                front.width,
+                columns.end - columns.start,
+                mask,
+            );
+            line.replace_range(bytes, &composed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn overlay_at_position_inside() {
+        let back = Block::of("abcd\nefgh\nijkl");
+
+        assert_eq!(
+            "abcd\neXYh\nijkl",
+            back.overlay_at(&Block::of("XY"), 1, 1).render()
+        );
+        assert_eq!(
+            "abcd\nefgh\nXjYl",
+            back.overlay_at(&Block::of("X Y"), 0, 2).render()
+        );
+    }
+
+    #[test]
+    fn overlay_at_grows_canvas() {
+        let b = Block::of("ab").overlay_at(&Block::of("XY\nZ"), 3, 1);
+
+        assert_eq!(5, b.width());
+        assert_eq!(vec!["ab   ", "   XY", "   Z "], *b.lines);
+
+        let b = Block::of("ab").overlay_at(&Block::of("X"), 0, 2);
+
+        assert_eq!(vec!["ab", "  ", "X "], *b.lines);
+    }
+
+    #[test]
+    fn overlay_at_clips_negative_offsets() {
+        let front = Block::of("123\n456\n789");
+        let b = Block::of("abc\ndef").overlay_at(&front, -1, -2);
+
+        assert_eq!(vec!["89c", "def"], *b.lines);
+        assert_eq!(
+            Block::of("abc\ndef"),
+            Block::of("abc\ndef").overlay_at(&front, -3, 0)
+        );
+    }
+
+    #[test]
+    fn compositing_transparent_characters() {
+        let front = Block::of("a.b:c");
+        let back = Block::of("12345");
+
+        assert_eq!(
+            "a2b4c",
+            front
+                .in_front_of_with(&back, &Compositing::transparent(".:"))
+                .render()
+        );
+        assert_eq!(
+            "12b4c",
+            front
+                .in_front_of_with(&back, &Compositing::transparent_if(|c| c < 'b'))
+                .render()
+        );
+    }
+
+    #[test]
+    fn compositing_underlay() {
+        let axis = Block::of("|\n|\n+---");
+        let label = Block::of("label");
+
+        assert_eq!(
+            "|label\n|\n+---",
+            axis.overlay_at_with(&label, 1, 0, &Compositing::underlay())
+                .render()
+        );
+        assert_eq!(
+            "世c",
+            Block::of("abc")
+                .in_front_of_with(&Block::of("世"), &Compositing::underlay())
+                .render()
+        );
+    }
+
+    #[test]
+    fn compositing_opaque() {
+        let front = Block::of("a b");
+
+        assert_eq!(
+            "a b4",
+            front
+                .in_front_of_with(&Block::of("1234"), &Compositing::opaque())
+                .render()
+        );
+    }
+
+    #[test]
+    fn compositing_mask() {
+        let front = Block::of("abc\ndef");
+        let mask = Block::of("x x\n x");
+        let back = Block::of("123\n456\n789");
+
+        assert_eq!(
+            "a2c\n4e6\n789",
+            front
+                .in_front_of_with(&back, &Compositing::mask(&mask))
+                .render()
+        );
+        assert_eq!(
+            "1c3\ne56",
+            Block::of("123\n456")
+                .overlay_at_with(&front, -1, 0, &Compositing::mask(&mask))
+                .render()
+        );
+    }
+
+    #[test]
+    fn overlay_at_wide_characters() {
+        let b = Block::of("世界").overlay_at(&Block::of("x"), 1, 0);
+
+        assert_eq!(" x界", b.render());
+    }
+
+    #[test]
+    fn overlay_at_recomposes_covered_columns_only() {
+        let back = Block::of("a世界b\nce\u{301}f");
+        let b = back.overlay_at(&Block::of("xy"), 2, 0);
+
+        assert_eq!(vec!["a xy b", "ce\u{301}f   "], *b.lines);
+        assert_eq!(
+            "e\u{301}x",
+            Block::of("e\u{301}")
+                .overlay_at(&Block::of("x"), 1, 0)
+                .render()
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::{fmt, io};
+
+use crate::{repeat, Block};
+
+/// Line ending used between rendered lines, see [RenderOptions].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// Line feed, `\n`.
+    Lf,
+    /// Carriage return and line feed, `\r\n`.
+    CrLf,
+}
+
+impl LineEnding {
+    /// Text of the line ending.
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Settings for rendering the text of a block, see [Block::render_with].
+///
+/// The default settings give the same text as [Block::render].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RenderOptions {
+    trim: bool,
+    line_ending: LineEnding,
+    trailing_newline: bool,
+    indent: String,
+    trailing_space_marker: Option<char>,
+}
+
+impl RenderOptions {
+    /// Create settings trimming whitespace on the right side of lines, with
+    /// lines separated by `\n` and no newline after the last line.
+    pub fn new() -> Self {
+        RenderOptions {
+            trim: true,
+            line_ending: LineEnding::Lf,
+            trailing_newline: false,
+            indent: String::new(),
+            trailing_space_marker: None,
+        }
+    }
+
+    /// Set if whitespace on the right side of lines is trimmed away, or if
+    /// lines are kept at the full width of the block.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Set line ending between lines.
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Set if there is a line ending after the last line.
+    pub fn trailing_newline(mut self, trailing_newline: bool) -> Self {
+        self.trailing_newline = trailing_newline;
+        self
+    }
+
+    /// Set prefix written before each line, e.g. spaces for indentation.
+    /// When trimming, whitespace on the right side of the prefix is trimmed
+    /// as well on lines that are empty after trimming.
+    pub fn indent(mut self, indent: &str) -> Self {
+        self.indent = indent.to_string();
+        self
+    }
+
+    /// Set character replacing each whitespace character on the right side
+    /// of lines, instead of trimming or keeping it, making it visible when
+    /// for example debugging.
+    pub fn trailing_space_marker(mut self, marker: Option<char>) -> Self {
+        self.trailing_space_marker = marker;
+        self
+    }
+
+    /// Write given line of a block according to the settings.
+    fn write_line<W: fmt::Write + ?Sized>(&self, line: &str, out: &mut W) -> fmt::Result {
+        let trimmed = line.trim_end();
+        let indent = match self.trailing_space_marker {
+            None if self.trim && trimmed.is_empty() => self.indent.trim_end(),
+            _ => &self.indent,
+        };
+        out.write_str(indent)?;
+        match self.trailing_space_marker {
+            Some(marker) => {
+                out.write_str(trimmed)?;
+                let spaces = line[trimmed.len()..].chars().count();
+                out.write_str(&repeat(marker, spaces))
+            }
+            None if self.trim => out.write_str(trimmed),
+            None => out.write_str(line),
+        }
+    }
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions::new()
+    }
+}
+
+impl Block {
+    /// Write the text of block to given formatter or string, like
+    /// [Block::render], one line at a time without building the whole text
+    /// first.
+    pub fn render_into<W: fmt::Write + ?Sized>(&self, out: &mut W) -> fmt::Result {
+        self.render_into_with(out, &RenderOptions::new())
+    }
+
+    /// Write the text of block to given writer, like [Block::render], one
+    /// line at a time without building the whole text first. Consider
+    /// wrapping unbuffered writers, like files, in a [std::io::BufWriter].
+    pub fn write_to<W: io::Write>(&self, writer: W) -> io::Result<()> {
+        self.write_to_with(writer, &RenderOptions::new())
+    }
+
+    /// Render a string from a block according to given settings.
+    pub fn render_with(&self, options: &RenderOptions) -> String {
+        let mut text = String::new();
+        self.render_into_with(&mut text, options)
+            .expect("writing to a string never fails");
+        text
+    }
+
+    /// Write the text of block to given formatter or string, like
+    /// [Block::render_into], according to given settings.
+    pub fn render_into_with<W: fmt::Write + ?Sized>(
+        &self,
+        out: &mut W,
+        options: &RenderOptions,
+    ) -> fmt::Result {
+        let line_ending = options.line_ending.as_str();
+        for (index, line) in self.lines.iter().enumerate() {
+            if index > 0 {
+                out.write_str(line_ending)?;
+            }
+            options.write_line(line, out)?;
+        }
+        if options.trailing_newline && !self.lines.is_empty() {
+            out.write_str(line_ending)?;
+        }
+        Ok(())
+    }
+
+    /// Write the text of block to given writer, like [Block::write_to],
+    /// according to given settings.
+    pub fn write_to_with<W: io::Write>(
+        &self,
+        mut writer: W,
+        options: &RenderOptions,
+    ) -> io::Result<()> {
+        let line_ending = options.line_ending.as_str();
+        let mut buffer = String::new();
+        for (index, line) in self.lines.iter().enumerate() {
+            buffer.clear();
+            if index > 0 {
+                buffer.push_str(line_ending);
+            }
+            options
+                .write_line(line, &mut buffer)
+                .expect("writing to a string never fails");
+            writer.write_all(buffer.as_bytes())?;
+        }
+        if options.trailing_newline && !self.lines.is_empty() {
+            writer.write_all(line_ending.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn render_into_string() {
+        let b = Block::of("a  \n\n b ").pad_bottom(1);
+        let mut text = String::from(">");
+        b.render_into(&mut text).unwrap();
+
+        assert_eq!(format!(">{}", b.render()), text);
+        assert_eq!(">a\n\n b\n", text);
+    }
+
+    #[test]
+    fn write_to_writer() {
+        let b = Block::of("a  \n世 ");
+        let mut bytes = Vec::new();
+        b.write_to(&mut bytes).unwrap();
+
+        assert_eq!(b.render().as_bytes(), &bytes[..]);
+        assert_eq!("a\n世", String::from_utf8(bytes).unwrap());
+    }
+
+    #[test]
+    fn render_with_default_options() {
+        let b = Block::of("a  \n\n b ");
+
+        assert_eq!(b.render(), b.render_with(&RenderOptions::default()));
+        assert_eq!("", Block::empty().render_with(&RenderOptions::new()));
+    }
+
+    #[test]
+    fn render_with_options() {
+        let b = Block::of("ab\nc");
+
+        let full_width = RenderOptions::new().trim(false);
+        assert_eq!("ab\nc ", b.render_with(&full_width));
+
+        let windows = RenderOptions::new()
+            .line_ending(LineEnding::CrLf)
+            .trailing_newline(true);
+        assert_eq!("ab\r\nc\r\n", b.render_with(&windows));
+
+        let indented = RenderOptions::new().indent("> ");
+        assert_eq!("> ab\n> c", b.render_with(&indented));
+        assert_eq!(">\n>", Block::of_height(2).render_with(&indented));
+        assert_eq!(
+            "> \n> ",
+            Block::of_height(2).render_with(&indented.clone().trim(false))
+        );
+
+        let marked = RenderOptions::new().trailing_space_marker(Some('·'));
+        assert_eq!("ab·\nc··", b.pad_right(1).render_with(&marked));
+    }
+
+    #[test]
+    fn write_to_with_options() {
+        let b = Block::of("ab\nc");
+        let options = RenderOptions::new()
+            .trim(false)
+            .indent(" ")
+            .line_ending(LineEnding::CrLf)
+            .trailing_newline(true);
+        let mut bytes = Vec::new();
+        b.write_to_with(&mut bytes, &options).unwrap();
+
+        assert_eq!(b.render_with(&options).as_bytes(), &bytes[..]);
+    }
+}
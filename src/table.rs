@@ -0,0 +1,1038 @@
+use crate::box_drawing::Arms;
+use crate::{
+    of_single_lines, repeat, subtract_or_zero, Block, BorderStyle, DecimalAlign, HAlign, Stroke,
+    VAlign,
+};
+
+/// Layout settings of a column in a [Table].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Column {
+    align: HAlign,
+    min_width: usize,
+    max_width: Option<usize>,
+    decimal: Option<DecimalAlign>,
+}
+
+impl Column {
+    /// Create column settings with cells aligned to the left and no limits
+    /// on the column width.
+    pub fn new() -> Self {
+        Column {
+            align: HAlign::Left,
+            min_width: 0,
+            max_width: None,
+            decimal: None,
+        }
+    }
+
+    /// Set horizontal alignment of the cells in the column.
+    pub fn align(mut self, align: HAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set minimum width of the column. Narrower cells are padded.
+    pub fn min_width(mut self, width: usize) -> Self {
+        self.min_width = width;
+        self
+    }
+
+    /// Set maximum width of the column. Wider cells are truncated, marked
+    /// with an ellipsis, see [Block::truncate_with_ellipsis].
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Align numbers in the column on their decimal separator, see
+    /// [Block::of_decimals]. Each line of the cells, except the header, is
+    /// taken as a number. The aligned numbers are then aligned as a whole
+    /// according to the horizontal alignment of the column.
+    pub fn decimal(mut self, align: DecimalAlign) -> Self {
+        self.decimal = Some(align);
+        self
+    }
+
+    /// Compute width of column from the widest cell content, respecting the
+    /// minimum and maximum width.
+    fn width(&self, content_width: usize) -> usize {
+        let width = content_width.max(self.min_width);
+        match self.max_width {
+            Some(max_width) => width.min(max_width.max(self.min_width)),
+            None => width,
+        }
+    }
+}
+
+impl Default for Column {
+    fn default() -> Self {
+        Column::new()
+    }
+}
+
+/// A cell of a [Table], possibly spanning several columns and rows.
+///
+/// Anything convertible to a block, like text, can be used as a cell
+/// spanning a single column and row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TableCell {
+    content: Block,
+    colspan: usize,
+    rowspan: usize,
+    align: Option<HAlign>,
+}
+
+impl TableCell {
+    /// Create cell with given content, spanning a single column and row.
+    pub fn new<T: Into<Block>>(content: T) -> Self {
+        TableCell {
+            content: content.into(),
+            colspan: 1,
+            rowspan: 1,
+            align: None,
+        }
+    }
+
+    /// Set number of columns spanned by the cell. Zero is treated as one. The
+    /// span is shortened if it would overlap a cell spanning several rows.
+    pub fn colspan(mut self, columns: usize) -> Self {
+        self.colspan = columns.max(1);
+        self
+    }
+
+    /// Set number of rows spanned by the cell. Zero is treated as one.
+    pub fn rowspan(mut self, rows: usize) -> Self {
+        self.rowspan = rows.max(1);
+        self
+    }
+
+    /// Set horizontal alignment of the cell content, overriding the
+    /// alignment of the column.
+    pub fn align(mut self, align: HAlign) -> Self {
+        self.align = Some(align);
+        self
+    }
+}
+
+impl From<Block> for TableCell {
+    fn from(content: Block) -> Self {
+        TableCell::new(content)
+    }
+}
+
+impl From<&Block> for TableCell {
+    fn from(content: &Block) -> Self {
+        TableCell::new(content)
+    }
+}
+
+impl From<&str> for TableCell {
+    fn from(content: &str) -> Self {
+        TableCell::new(content)
+    }
+}
+
+impl From<String> for TableCell {
+    fn from(content: String) -> Self {
+        TableCell::new(content)
+    }
+}
+
+impl From<char> for TableCell {
+    fn from(content: char) -> Self {
+        TableCell::new(content)
+    }
+}
+
+/// A table of cells, laid out in rows and columns, that can be turned into a
+/// [Block].
+///
+/// Each column gets the width of its widest cell, unless limited by the
+/// column settings, see [Column]. Cells in the same row are aligned
+/// vertically according to the vertical alignment of the table. Cells can
+/// span several columns and rows, see [TableCell].
+///
+/// ```
+/// use text_block_layout::{Column, HAlign, Table};
+///
+/// let table = Table::new()
+///     .column(Column::new())
+///     .column(Column::new().align(HAlign::Right))
+///     .row(vec!["Apples", "12"])
+///     .row(vec!["Pears", "7"]);
+///
+/// assert_eq!("Apples 12\nPears   7", table.to_block().render());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Table {
+    columns: Vec<Column>,
+    header: Option<Vec<TableCell>>,
+    rows: Vec<Vec<TableCell>>,
+    valign: VAlign,
+    spacing: usize,
+    border: BorderStyle,
+    padding: usize,
+}
+
+/// A cell placed in the grid of a table.
+struct Placement<'a> {
+    cell: &'a TableCell,
+    row: usize,
+    column: usize,
+    rows: usize,
+    columns: usize,
+}
+
+/// Cells placed in the grid of a table, with the index of the placement
+/// covering each slot in the grid.
+struct Grid<'a> {
+    placements: Vec<Placement<'a>>,
+    owners: Vec<Vec<usize>>,
+    column_count: usize,
+}
+
+impl<'a> Grid<'a> {
+    /// Place cells of given rows in a grid, left to right and top to bottom,
+    /// skipping slots already covered by cells spanning several rows. Slots
+    /// not covered by any cell get an empty cell.
+    fn new(rows: &[&'a Vec<TableCell>], empty: &'a TableCell) -> Self {
+        let row_count = rows.len();
+        let mut placements: Vec<Placement> = Vec::new();
+        let mut owners: Vec<Vec<Option<usize>>> = vec![vec![]; row_count];
+
+        for (row, cells) in rows.iter().enumerate() {
+            let mut column = 0;
+            for cell in cells.iter() {
+                while owners[row].get(column).copied().flatten().is_some() {
+                    column += 1;
+                }
+                let rows = cell.rowspan.min(row_count - row);
+
+                // Shorten span to not overlap cells spanning from rows above
+                let is_free = |c: usize| {
+                    owners[row..row + rows]
+                        .iter()
+                        .all(|slots| slots.get(c).copied().flatten().is_none())
+                };
+                let columns = (column..column + cell.colspan)
+                    .take_while(|&c| is_free(c))
+                    .count();
+
+                for slots in owners.iter_mut().skip(row).take(rows) {
+                    let end = column + columns;
+                    if slots.len() < end {
+                        slots.resize(end, None);
+                    }
+                    for slot in &mut slots[column..end] {
+                        *slot = Some(placements.len());
+                    }
+                }
+                placements.push(Placement {
+                    cell,
+                    row,
+                    column,
+                    rows,
+                    columns,
+                });
+                column += columns;
+            }
+        }
+
+        let column_count = owners.iter().map(Vec::len).max().unwrap_or(0);
+        let owners = owners
+            .into_iter()
+            .enumerate()
+            .map(|(row, mut slots)| {
+                slots.resize(column_count, None);
+                slots
+                    .into_iter()
+                    .enumerate()
+                    .map(|(column, slot)| {
+                        slot.unwrap_or_else(|| {
+                            placements.push(Placement {
+                                cell: empty,
+                                row,
+                                column,
+                                rows: 1,
+                                columns: 1,
+                            });
+                            placements.len() - 1
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Grid {
+            placements,
+            owners,
+            column_count,
+        }
+    }
+
+    /// Tell if the slots, given as row and column, are covered by the same
+    /// cell.
+    fn same(&self, a: (usize, usize), b: (usize, usize)) -> bool {
+        self.owners[a.0][a.1] == self.owners[b.0][b.1]
+    }
+
+    /// Return cell covering slot of given row and column.
+    fn owner(&self, row: usize, column: usize) -> &Placement<'a> {
+        &self.placements[self.owners[row][column]]
+    }
+}
+
+/// Grow sizes within given range by one at a time, round robin, until the
+/// missing size is covered or no size can grow any further.
+fn grow(sizes: &mut [usize], limits: &[usize], mut missing: usize) {
+    while missing > 0 {
+        let mut grown = false;
+        for (size, limit) in sizes.iter_mut().zip(limits) {
+            if missing > 0 && *size < *limit {
+                *size += 1;
+                missing -= 1;
+                grown = true;
+            }
+        }
+        if !grown {
+            break;
+        }
+    }
+}
+
+impl Table {
+    /// Create empty table without borders, with columns separated by one
+    /// space and cells aligned at the top.
+    pub fn new() -> Self {
+        Table {
+            columns: vec![],
+            header: None,
+            rows: vec![],
+            valign: VAlign::Top,
+            spacing: 1,
+            border: BorderStyle::none(),
+            padding: 1,
+        }
+    }
+
+    /// Add settings for the next column. Columns without settings use the
+    /// default settings, see [Column::new].
+    pub fn column(mut self, column: Column) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Set header row of cells at the top of the table, separated from the
+    /// other rows by the header rule of the border style.
+    pub fn header<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<TableCell>,
+    {
+        self.header = Some(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Add a row of cells at the bottom of the table. Cells are placed in
+    /// the leftmost columns not already covered by cells spanning several
+    /// rows from the rows above.
+    pub fn row<I>(mut self, cells: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<TableCell>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Set vertical alignment of cells of different height within a row.
+    pub fn valign(mut self, align: VAlign) -> Self {
+        self.valign = align;
+        self
+    }
+
+    /// Set number of spaces between columns not separated by a rule.
+    pub fn spacing(mut self, spacing: usize) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Set style of the borders and rules of the table.
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        self.border = border;
+        self
+    }
+
+    /// Set number of spaces between cell content and vertical lines.
+    pub fn padding(mut self, padding: usize) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Return number of columns, including columns covered by cells spanning
+    /// several columns and any header.
+    pub fn column_count(&self) -> usize {
+        let empty = TableCell::new(Block::empty());
+        Grid::new(&self.all_rows(), &empty).column_count
+    }
+
+    /// Return number of rows, excluding any header.
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Return all rows, starting with any header.
+    fn all_rows(&self) -> Vec<&Vec<TableCell>> {
+        self.header.iter().chain(self.rows.iter()).collect()
+    }
+
+    /// Return settings of column with given index.
+    fn column_settings(&self, index: usize) -> Column {
+        self.columns.get(index).cloned().unwrap_or_default()
+    }
+
+    /// Return stroke of the vertical line at given column boundary, where
+    /// boundary 0 is the left side of the table.
+    fn vertical_rule(&self, boundary: usize, column_count: usize) -> Option<Stroke> {
+        if boundary == 0 {
+            self.border.left
+        } else if boundary == column_count {
+            self.border.right
+        } else {
+            self.border.columns
+        }
+    }
+
+    /// Return stroke of the horizontal line at given row boundary, where
+    /// boundary 0 is the top side of the table.
+    fn horizontal_rule(&self, boundary: usize, row_count: usize) -> Option<Stroke> {
+        if boundary == 0 {
+            self.border.top
+        } else if boundary == row_count {
+            self.border.bottom
+        } else if boundary == 1 && self.header.is_some() {
+            self.border.header
+        } else {
+            self.border.rows
+        }
+    }
+
+    /// Lay out the table as a block.
+    pub fn to_block(&self) -> Block {
+        let empty = TableCell::new(Block::empty());
+        let rows = self.all_rows();
+        let grid = Grid::new(&rows, &empty);
+
+        if grid.column_count == 0 {
+            return Block::empty();
+        }
+        of_single_lines(Layout::new(self, &grid, rows.len()).lines())
+    }
+}
+
+/// Sizes of the columns and rows of a table, computed from its cells.
+struct Layout<'t, 'a> {
+    table: &'t Table,
+    grid: &'t Grid<'a>,
+    column_count: usize,
+    row_count: usize,
+    settings: Vec<Column>,
+    /// Content of each placed cell, with numbers aligned in decimal columns.
+    contents: Vec<Block>,
+    /// Spaces on the left and right side of the cells in each column.
+    paddings: Vec<(usize, usize)>,
+    widths: Vec<usize>,
+    heights: Vec<usize>,
+}
+
+impl<'t, 'a> Layout<'t, 'a> {
+    fn new(table: &'t Table, grid: &'t Grid<'a>, row_count: usize) -> Self {
+        let column_count = grid.column_count;
+        let mut layout = Layout {
+            table,
+            grid,
+            column_count,
+            row_count,
+            settings: (0..column_count)
+                .map(|column| table.column_settings(column))
+                .collect(),
+            contents: grid
+                .placements
+                .iter()
+                .map(|p| p.cell.content.clone())
+                .collect(),
+            paddings: vec![],
+            widths: vec![0; column_count],
+            heights: vec![0; row_count],
+        };
+
+        layout.paddings = (0..column_count)
+            .map(|column| {
+                let left = match layout.vertical(column) {
+                    Some(_) => table.padding,
+                    None if column > 0 => table.spacing,
+                    None => 0,
+                };
+                let right = match layout.vertical(column + 1) {
+                    Some(_) => table.padding,
+                    None => 0,
+                };
+                (left, right)
+            })
+            .collect();
+
+        layout.align_decimals();
+        layout.compute_widths();
+        layout.compute_heights();
+        layout
+    }
+
+    /// Return stroke of the vertical line at given column boundary.
+    fn vertical(&self, boundary: usize) -> Option<Stroke> {
+        self.table.vertical_rule(boundary, self.column_count)
+    }
+
+    /// Return stroke of the horizontal line at given row boundary.
+    fn horizontal(&self, boundary: usize) -> Option<Stroke> {
+        self.table.horizontal_rule(boundary, self.row_count)
+    }
+
+    /// Width of the content area of a cell, including columns and vertical
+    /// lines it spans, but excluding its outer paddings.
+    fn content_width(&self, placement: &Placement) -> usize {
+        let (from, to) = (placement.column, placement.column + placement.columns);
+        let columns = (from..to)
+            .map(|c| self.paddings[c].0 + self.widths[c] + self.paddings[c].1)
+            .sum::<usize>();
+        let lines = (from + 1..to)
+            .filter(|&b| self.vertical(b).is_some())
+            .count();
+
+        columns + lines - self.paddings[from].0 - self.paddings[to - 1].1
+    }
+
+    /// Height of the content area of a cell, including rows and horizontal
+    /// lines it spans.
+    fn content_height(&self, placement: &Placement) -> usize {
+        let (from, to) = (placement.row, placement.row + placement.rows);
+        let rows = self.heights[from..to].iter().sum::<usize>();
+        let lines = (from + 1..to)
+            .filter(|&b| self.horizontal(b).is_some())
+            .count();
+
+        rows + lines
+    }
+
+    /// Align numbers on their decimal separator in the cells, except the
+    /// header, of columns with decimal alignment.
+    fn align_decimals(&mut self) {
+        let first_row = usize::from(self.table.header.is_some());
+
+        for (column, setting) in self.settings.iter().enumerate() {
+            let decimal = match &setting.decimal {
+                Some(decimal) => decimal,
+                None => continue,
+            };
+            let indices = (0..self.grid.placements.len())
+                .filter(|&i| {
+                    let p = &self.grid.placements[i];
+                    p.column == column && p.columns == 1 && p.row >= first_row
+                })
+                .collect::<Vec<usize>>();
+
+            let lines = indices
+                .iter()
+                .flat_map(|&i| self.contents[i].lines.iter().map(|line| line.trim_end()))
+                .collect::<Vec<&str>>();
+            let mut aligned = decimal.align_lines(&lines).into_iter();
+
+            for i in indices {
+                let height = self.contents[i].height();
+                self.contents[i] = of_single_lines(aligned.by_ref().take(height).collect());
+            }
+        }
+    }
+
+    /// Compute column widths, first from cells in a single column and then
+    /// widened for cells spanning several columns, as far as the maximum
+    /// widths of the columns allow.
+    fn compute_widths(&mut self) {
+        for (column, setting) in self.settings.iter().enumerate() {
+            let content = self
+                .grid
+                .placements
+                .iter()
+                .zip(&self.contents)
+                .filter(|(p, _)| p.column == column && p.columns == 1)
+                .map(|(_, content)| content.width())
+                .max()
+                .unwrap_or(0);
+            self.widths[column] = setting.width(content);
+        }
+
+        let mut spanning = self
+            .grid
+            .placements
+            .iter()
+            .zip(&self.contents)
+            .filter(|(p, _)| p.columns > 1)
+            .collect::<Vec<_>>();
+        spanning.sort_by_key(|(p, _)| p.columns);
+
+        for (placement, content) in spanning {
+            let content = content.width();
+            let missing = subtract_or_zero(content, self.content_width(placement));
+            let range = placement.column..placement.column + placement.columns;
+            let limits = self.settings[range.clone()]
+                .iter()
+                .map(|setting| setting.max_width.unwrap_or(usize::MAX))
+                .collect::<Vec<usize>>();
+            grow(&mut self.widths[range], &limits, missing);
+        }
+    }
+
+    /// Compute row heights, first from cells in a single row and then
+    /// heightened for cells spanning several rows.
+    fn compute_heights(&mut self) {
+        for row in 0..self.row_count {
+            self.heights[row] = self
+                .grid
+                .placements
+                .iter()
+                .zip(&self.contents)
+                .filter(|(p, _)| p.row == row && p.rows == 1)
+                .map(|(_, content)| content.height())
+                .max()
+                .unwrap_or(0);
+        }
+
+        let mut spanning = self
+            .grid
+            .placements
+            .iter()
+            .zip(&self.contents)
+            .filter(|(p, _)| p.rows > 1)
+            .collect::<Vec<_>>();
+        spanning.sort_by_key(|(p, _)| p.rows);
+
+        for (placement, content) in spanning {
+            let content = content.height();
+            let missing = subtract_or_zero(content, self.content_height(placement));
+            let range = placement.row..placement.row + placement.rows;
+            grow(
+                &mut self.heights[range],
+                &vec![usize::MAX; placement.rows],
+                missing,
+            );
+        }
+    }
+
+    /// Fit cell of given placement index into its area, including outer
+    /// paddings.
+    fn cell_block(&self, index: usize) -> Block {
+        let placement = &self.grid.placements[index];
+        let width = self.content_width(placement);
+        let align = placement
+            .cell
+            .align
+            .unwrap_or(self.settings[placement.column].align);
+        let (left, _) = self.paddings[placement.column];
+        let (_, right) = self.paddings[placement.column + placement.columns - 1];
+
+        self.contents[index]
+            .truncate_with_ellipsis(width)
+            .pad_to_width(width, align)
+            .pad_to_height(self.content_height(placement), self.table.valign)
+            .pad_left(left)
+            .pad_right(right)
+    }
+
+    /// Tell if the cells on both sides of given column boundary in given row
+    /// are the same cell, spanning several columns.
+    fn merged_in_row(&self, row: usize, boundary: usize) -> bool {
+        boundary > 0
+            && boundary < self.column_count
+            && self.grid.same((row, boundary - 1), (row, boundary))
+    }
+
+    /// Tell if the cells on both sides of given row boundary in given column
+    /// are the same cell, spanning several rows.
+    fn merged_in_column(&self, boundary: usize, column: usize) -> bool {
+        boundary > 0
+            && boundary < self.row_count
+            && self.grid.same((boundary - 1, column), (boundary, column))
+    }
+
+    /// Lay out all lines of the table.
+    fn lines(&self) -> Vec<String> {
+        let blocks = (0..self.grid.placements.len())
+            .map(|index| self.cell_block(index))
+            .collect::<Vec<Block>>();
+
+        let mut row_tops = vec![0; self.row_count];
+
+        // Line of cell covering given slot at given line of the table, given
+        // only for the leftmost column of the cell.
+        let cell_line = |row_tops: &[usize], row, column, y: usize| {
+            let placement = self.grid.owner(row, column);
+            if placement.column == column {
+                let block = &blocks[self.grid.owners[row][column]];
+                block.lines[y - row_tops[placement.row]].clone()
+            } else {
+                String::new()
+            }
+        };
+
+        let mut lines = Vec::new();
+        for boundary in 0..=self.row_count {
+            if let Some(stroke) = self.horizontal(boundary) {
+                let mut line = String::new();
+                for column in 0..=self.column_count {
+                    line.push_str(&self.junction(boundary, column, stroke));
+                    if column == self.column_count {
+                        break;
+                    }
+                    if self.merged_in_column(boundary, column) {
+                        line += &cell_line(&row_tops, boundary, column, lines.len());
+                    } else {
+                        let (left, right) = self.paddings[column];
+                        let width = left + self.widths[column] + right;
+                        line += &repeat(self.table.border.horizontal(Some(stroke)), width);
+                    }
+                }
+                lines.push(line);
+            }
+
+            if boundary == self.row_count {
+                break;
+            }
+            row_tops[boundary] = lines.len();
+            for _ in 0..self.heights[boundary] {
+                let mut line = String::new();
+                for column in 0..=self.column_count {
+                    if !self.merged_in_row(boundary, column) {
+                        if let Some(stroke) = self.vertical(column) {
+                            line.push(self.table.border.vertical(Some(stroke)));
+                        }
+                    }
+                    if column < self.column_count {
+                        line += &cell_line(&row_tops, boundary, column, lines.len());
+                    }
+                }
+                lines.push(line);
+            }
+        }
+        lines
+    }
+
+    /// Return junction where vertical line at given column boundary crosses
+    /// horizontal line of given stroke at given row boundary. Returns empty
+    /// text if there is no vertical line or if the junction is covered by a
+    /// cell spanning several rows and columns.
+    fn junction(&self, boundary: usize, column: usize, stroke: Stroke) -> String {
+        let vertical = match self.vertical(column) {
+            Some(vertical) => vertical,
+            None => return String::new(),
+        };
+
+        let (above, below) = (boundary > 0, boundary < self.row_count);
+        let left_merged = column > 0 && self.merged_in_column(boundary, column - 1);
+        let right_merged = column < self.column_count && self.merged_in_column(boundary, column);
+
+        let covered = left_merged
+            && right_merged
+            && self
+                .grid
+                .same((boundary - 1, column - 1), (boundary, column));
+        if covered {
+            return String::new();
+        }
+
+        let arms = Arms {
+            up: Some(vertical).filter(|_| above && !self.merged_in_row(boundary - 1, column)),
+            right: Some(stroke).filter(|_| column < self.column_count && !right_merged),
+            down: Some(vertical).filter(|_| below && !self.merged_in_row(boundary, column)),
+            left: Some(stroke).filter(|_| column > 0 && !left_merged),
+        };
+        self.table.border.glyph(arms).to_string()
+    }
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table::new()
+    }
+}
+
+impl From<&Table> for Block {
+    fn from(table: &Table) -> Self {
+        table.to_block()
+    }
+}
+
+impl From<Table> for Block {
+    fn from(table: Table) -> Self {
+        table.to_block()
+    }
+}
+
+impl std::fmt::Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_block())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn column_width_from_content() {
+        let table = Table::new()
+            .row(vec!["a", "bbb", "c"])
+            .row(vec!["aaaa", "b"]);
+
+        assert_eq!(3, table.column_count());
+        assert_eq!(2, table.row_count());
+        assert_eq!(vec!["a    bbb c", "aaaa b    "], *table.to_block().lines);
+    }
+
+    #[test]
+    fn column_alignment() {
+        let table = Table::new()
+            .column(Column::new().align(HAlign::Right))
+            .column(Column::new().align(HAlign::CenterLeft))
+            .row(vec!["a", "b"])
+            .row(vec!["aaa", "bbbb"]);
+
+        assert_eq!(vec!["  a  b  ", "aaa bbbb"], *table.to_block().lines);
+    }
+
+    #[test]
+    fn decimal_column_alignment() {
+        let table = Table::new()
+            .column(Column::new())
+            .column(
+                Column::new()
+                    .align(HAlign::Right)
+                    .decimal(DecimalAlign::new()),
+            )
+            .header(vec!["item", "price"])
+            .row(vec!["tea", "3.5"])
+            .row(vec!["cake", "12.25"]);
+
+        assert_eq!(
+            vec!["item price", "tea   3.5 ", "cake 12.25"],
+            *table.to_block().lines
+        );
+    }
+
+    #[test]
+    fn column_min_and_max_width() {
+        let table = Table::new()
+            .column(Column::new().min_width(4))
+            .column(Column::new().max_width(3))
+            .row(vec!["a", "bbbbb"]);
+
+        assert_eq!(vec!["a    bb…"], *table.to_block().lines);
+    }
+
+    #[test]
+    fn multi_line_cells_are_aligned_vertically() {
+        let table = Table::new()
+            .valign(VAlign::Bottom)
+            .spacing(2)
+            .row(vec![Block::of("a\na\na"), Block::of("b")]);
+
+        assert_eq!("a\na\na  b", table.to_string());
+    }
+
+    fn bordered() -> Table {
+        Table::new()
+            .header(vec!["a", "b"])
+            .row(vec!["1", "22"])
+            .row(vec!["333", "4"])
+    }
+
+    #[test]
+    fn single_border() {
+        let table = bordered().border(BorderStyle::single());
+
+        assert_eq!(
+            "┌─────┬────┐\n\
+             │ a   │ b  │\n\
+             ├─────┼────┤\n\
+             │ 1   │ 22 │\n\
+             │ 333 │ 4  │\n\
+             └─────┴────┘",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn mixed_strokes_border() {
+        let style = BorderStyle::double()
+            .columns(Some(Stroke::Light))
+            .rows(Some(Stroke::Light));
+        let table = bordered().border(style).padding(0);
+
+        assert_eq!(
+            "╔═══╤══╗\n\
+             ║a  │b ║\n\
+             ╠═══╪══╣\n\
+             ║1  │22║\n\
+             ╟───┼──╢\n\
+             ║333│4 ║\n\
+             ╚═══╧══╝",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn ascii_border() {
+        let table = bordered().border(BorderStyle::ascii().rows(Some(Stroke::Light)));
+
+        assert_eq!(
+            "+-----+----+\n\
+             | a   | b  |\n\
+             +=====+====+\n\
+             | 1   | 22 |\n\
+             +-----+----+\n\
+             | 333 | 4  |\n\
+             +-----+----+",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn markdown_border() {
+        let table = bordered().border(BorderStyle::markdown());
+
+        assert_eq!(
+            "| a   | b  |\n\
+             |-----|----|\n\
+             | 1   | 22 |\n\
+             | 333 | 4  |",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn rounded_border_without_inner_lines() {
+        let style = BorderStyle::rounded().header(None).columns(None);
+        let table = bordered().border(style).spacing(2);
+
+        assert_eq!(
+            "╭─────────╮\n\
+             │ a    b  │\n\
+             │ 1    22 │\n\
+             │ 333  4  │\n\
+             ╰─────────╯",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn header_rule_only() {
+        let style = BorderStyle::none().header(Some(Stroke::Heavy));
+        let table = bordered().border(style);
+
+        assert_eq!("a   b\n━━━━━━\n1   22\n333 4", table.to_string());
+    }
+
+    #[test]
+    fn column_span() {
+        let table = Table::new()
+            .border(BorderStyle::single().rows(Some(Stroke::Light)))
+            .header(vec![TableCell::new("Fruit").colspan(2)])
+            .row(vec!["Apples", "12"])
+            .row(vec![TableCell::new("Total 19")
+                .colspan(2)
+                .align(HAlign::Right)]);
+
+        assert_eq!(
+            "┌─────────────┐\n\
+             │ Fruit       │\n\
+             ├────────┬────┤\n\
+             │ Apples │ 12 │\n\
+             ├────────┴────┤\n\
+             │    Total 19 │\n\
+             └─────────────┘",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn column_span_widens_columns() {
+        let table = Table::new()
+            .border(BorderStyle::ascii())
+            .row(vec![TableCell::new("abcdefghij").colspan(2)])
+            .row(vec!["a", "b"]);
+
+        assert_eq!(
+            "+------------+\n\
+             | abcdefghij |\n\
+             | a    | b   |\n\
+             +------+-----+",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn row_span() {
+        let table = Table::new()
+            .border(BorderStyle::single().rows(Some(Stroke::Light)))
+            .row(vec![TableCell::new("Fruit").rowspan(2), "Apple".into()])
+            .row(vec!["Pear"])
+            .row(vec!["Nut", "Peanut"]);
+
+        assert_eq!(
+            "┌───────┬────────┐\n\
+             │ Fruit │ Apple  │\n\
+             │       ├────────┤\n\
+             │       │ Pear   │\n\
+             ├───────┼────────┤\n\
+             │ Nut   │ Peanut │\n\
+             └───────┴────────┘",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn row_and_column_span() {
+        let table = Table::new()
+            .border(BorderStyle::single().rows(Some(Stroke::Light)))
+            .row(vec![
+                TableCell::new("a\na\na").rowspan(2).colspan(2),
+                "b".into(),
+            ])
+            .row(vec!["c"])
+            .row(vec!["d", "e", "f"]);
+
+        assert_eq!(
+            "┌───────┬───┐\n\
+             │ a     │ b │\n\
+             │ a     ├───┤\n\
+             │ a     │ c │\n\
+             ├───┬───┼───┤\n\
+             │ d │ e │ f │\n\
+             └───┴───┴───┘",
+            table.to_string()
+        );
+    }
+
+    #[test]
+    fn overlapping_spans_are_shortened() {
+        let table = Table::new()
+            .row(vec!["a".into(), TableCell::new("b").rowspan(2)])
+            .row(vec![TableCell::new("cc").colspan(2), "d".into()]);
+
+        assert_eq!(3, table.column_count());
+        assert_eq!("a  b\ncc   d", table.to_string());
+    }
+
+    #[test]
+    fn empty_table() {
+        assert_eq!(Block::empty(), Table::new().to_block());
+    }
+}
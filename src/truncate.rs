@@ -0,0 +1,163 @@
+use crate::cell::{columns_text, grapheme_starts, line_cells, Cell};
+use crate::{line_width, of_single_lines, Block};
+
+/// Position of the marker showing where text has been cut away when
+/// truncating a block, see [Block::truncate_with_marker].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EllipsisPosition {
+    /// Cut away the start of lines, e.g. `…/src/lib.rs`.
+    Start,
+    /// Cut away the middle of lines, e.g. `/home/…/lib.rs`.
+    Middle,
+    /// Cut away the end of lines, e.g. `/home/user/…`.
+    End,
+}
+
+/// Return text of the grapheme clusters fully inside the range of columns
+/// `begin..end` of given cells.
+fn graphemes_within(cells: &[Cell], begin: usize, end: usize) -> String {
+    let starts = grapheme_starts(cells);
+
+    (begin..end.min(cells.len()))
+        .filter(|&x| starts[x] == x && x + cells[x].width() <= end)
+        .map(|x| cells[x].text())
+        .collect()
+}
+
+/// Truncate a single line of given width, so that it fits in the new width,
+/// using a marker where text is cut away.
+fn truncate_line(
+    line: &str,
+    current_width: usize,
+    width: usize,
+    marker: &str,
+    position: EllipsisPosition,
+) -> String {
+    let cells = line_cells(line, current_width);
+    let content_width = line_width(line.trim_end());
+
+    if content_width <= width {
+        return graphemes_within(&cells, 0, width);
+    }
+
+    let marker_width = line_width(marker);
+    if marker_width >= width {
+        return graphemes_within(&line_cells(marker, marker_width), 0, width);
+    }
+
+    let keep = width - marker_width;
+    let head = |columns| graphemes_within(&cells, 0, columns);
+    let tail = |columns| graphemes_within(&cells, content_width - columns, content_width);
+
+    match position {
+        EllipsisPosition::Start => marker.to_string() + &tail(keep),
+        EllipsisPosition::Middle => head(keep - keep / 2) + marker + &tail(keep / 2),
+        EllipsisPosition::End => head(keep) + marker,
+    }
+}
+
+impl Block {
+    /// Cut away the right side of the block so that given width is reached.
+    /// Narrower block is untouched. Wide characters cut in half are replaced
+    /// with spaces.
+    pub fn truncate_to_width(&self, width: usize) -> Self {
+        if self.width <= width {
+            return self.clone();
+        }
+
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| columns_text(&line_cells(line, self.width), 0, width))
+            .collect::<Vec<String>>();
+
+        Block::new(width, lines)
+    }
+
+    /// Cut away the end of lines so that given width is reached, marking
+    /// lines that are cut with an ellipsis character `…`. Narrower block is
+    /// untouched. See [Block::truncate_with_marker].
+    pub fn truncate_with_ellipsis(&self, width: usize) -> Self {
+        self.truncate_with_marker(width, "…", EllipsisPosition::End)
+    }
+
+    /// Cut away text from lines so that given width is reached. Narrower
+    /// block is untouched. Lines whose text, ignoring trailing whitespace,
+    /// does not fit are marked with given marker at given position. Wide
+    /// characters are never cut in half, possibly leaving lines with some
+    /// trailing padding.
+    pub fn truncate_with_marker(
+        &self,
+        width: usize,
+        marker: &str,
+        position: EllipsisPosition,
+    ) -> Self {
+        if self.width <= width {
+            return self.clone();
+        }
+
+        let lines = self
+            .lines
+            .iter()
+            .map(|line| truncate_line(line, self.width, width, marker, position))
+            .collect::<Vec<String>>();
+
+        of_single_lines(lines).pad_to_width_right(width)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn truncate_to_width() {
+        let b = Block::of("abcdef").add_text("a世").add_text("x");
+
+        assert_eq!(vec!["ab", "a ", "x "], *b.truncate_to_width(2).lines);
+        assert_eq!(b, b.truncate_to_width(6));
+        assert_eq!(b, b.truncate_to_width(7));
+    }
+
+    #[test]
+    fn truncate_with_ellipsis() {
+        let b = Block::of("abcdef").add_text("abc");
+
+        assert_eq!(vec!["abc…", "abc "], *b.truncate_with_ellipsis(4).lines);
+    }
+
+    #[test]
+    fn truncate_with_marker_at_start_and_middle() {
+        let b = Block::of("/home/user/src/lib.rs");
+
+        let start = b.truncate_with_marker(12, "...", EllipsisPosition::Start);
+        let middle = b.truncate_with_marker(12, "…", EllipsisPosition::Middle);
+
+        assert_eq!("...rc/lib.rs", start.render());
+        assert_eq!("/home/…ib.rs", middle.render());
+        assert_eq!(12, start.width());
+    }
+
+    #[test]
+    fn truncate_with_ellipsis_does_not_split_wide_characters() {
+        let b = Block::of("世界世界");
+
+        assert_eq!(vec!["世… "], *b.truncate_with_ellipsis(4).lines);
+        assert_eq!(
+            vec!["…世界 "],
+            *b.truncate_with_marker(6, "…", EllipsisPosition::Start)
+                .lines
+        );
+    }
+
+    #[test]
+    fn truncate_with_too_wide_marker() {
+        let b = Block::of("abcdef");
+
+        assert_eq!(
+            vec!["<<"],
+            *b.truncate_with_marker(2, "<<<", EllipsisPosition::End)
+                .lines
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{line_width, of_single_lines, repeat, split_lines, Block, HAlign};
+
+/// Alignment of the lines in wrapped text, see [Block::wrap_aligned].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TextAlign {
+    /// Align lines to the left side, see [Block::pad_to_width_right].
+    Left,
+    /// Align lines to the right side, see [Block::pad_to_width_left].
+    Right,
+    /// Center lines. If padding needs to be uneven, there will be more
+    /// padding on the right side, see [Block::pad_to_width_center_right].
+    CenterLeft,
+    /// Center lines. If padding needs to be uneven, there will be more
+    /// padding on the left side, see [Block::pad_to_width_center_left].
+    CenterRight,
+    /// Fill the whole width of lines by distributing extra spaces between
+    /// the words, with any uneven spaces going to the leftmost gaps. The last
+    /// line of each paragraph, and lines with a single word, are aligned to
+    /// the left.
+    Justify,
+}
+
+impl From<HAlign> for TextAlign {
+    fn from(align: HAlign) -> Self {
+        match align {
+            HAlign::Left => TextAlign::Left,
+            HAlign::Right => TextAlign::Right,
+            HAlign::CenterLeft => TextAlign::CenterLeft,
+            HAlign::CenterRight => TextAlign::CenterRight,
+        }
+    }
+}
+
+/// Tell if character is whitespace where a line may be broken. Non-breaking
+/// spaces are not.
+fn is_break(c: char) -> bool {
+    c.is_whitespace() && !matches!(c, '\u{a0}' | '\u{2007}' | '\u{202f}')
+}
+
+/// Split word into pieces of at most given display width, breaking between
+/// grapheme clusters. A single grapheme cluster wider than the width gets a
+/// piece of its own.
+fn split_word(word: &str, width: usize) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut begin = 0;
+    let mut piece_width = 0;
+
+    for (index, grapheme) in word.grapheme_indices(true) {
+        let grapheme_width = line_width(grapheme);
+        if piece_width > 0 && piece_width + grapheme_width > width {
+            pieces.push(&word[begin..index]);
+            begin = index;
+            piece_width = 0;
+        }
+        piece_width += grapheme_width;
+    }
+    pieces.push(&word[begin..]);
+    pieces
+}
+
+/// Wrap a paragraph, not containing any newlines, into lines of words of at
+/// most given display width. Words are separated by a single space.
+fn wrap_paragraph(paragraph: &str, width: usize) -> Vec<Vec<&str>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<&str> = Vec::new();
+    let mut current_width = 0;
+
+    let words = paragraph.split(is_break).filter(|word| !word.is_empty());
+
+    for piece in words.flat_map(|word| split_word(word, width)) {
+        let piece_width = line_width(piece);
+
+        if line.is_empty() {
+            current_width = piece_width;
+        } else if current_width + 1 + piece_width <= width {
+            current_width += 1 + piece_width;
+        } else {
+            lines.push(std::mem::take(&mut line));
+            current_width = piece_width;
+        }
+        line.push(piece);
+    }
+    lines.push(line);
+    lines
+}
+
+/// Join words with spaces, distributing extra spaces between the words so
+/// that the line gets given display width.
+fn justify(words: &[&str], width: usize) -> String {
+    let gaps = words.len().saturating_sub(1);
+    if gaps == 0 {
+        return words.concat();
+    }
+
+    let words_width = words.iter().map(|word| line_width(word)).sum::<usize>();
+    let spaces = width.saturating_sub(words_width).max(gaps);
+    let extra_spaces = spaces % gaps;
+
+    let mut line = words[0].to_string();
+    for (gap, word) in words[1..].iter().enumerate() {
+        let gap_width = spaces / gaps + usize::from(gap < extra_spaces);
+        line += &repeat(' ', gap_width);
+        line += word;
+    }
+    line
+}
+
+/// Align a line of words to given width.
+fn align_line(words: &[&str], width: usize, align: TextAlign, is_last: bool) -> Block {
+    let line = || of_single_lines(vec![words.join(" ")]);
+
+    match align {
+        TextAlign::Left => line().pad_to_width(width, HAlign::Left),
+        TextAlign::Right => line().pad_to_width(width, HAlign::Right),
+        TextAlign::CenterLeft => line().pad_to_width(width, HAlign::CenterLeft),
+        TextAlign::CenterRight => line().pad_to_width(width, HAlign::CenterRight),
+        TextAlign::Justify if is_last => line().pad_to_width(width, HAlign::Left),
+        TextAlign::Justify => of_single_lines(vec![justify(words, width)]),
+    }
+}
+
+impl Block {
+    /// Create block of given width containing text wrapped into lines,
+    /// breaking lines on whitespace. Newlines in the text are kept as hard
+    /// line breaks. Words wider than the given width are broken into several
+    /// lines.
+    ///
+    /// The block will be of exactly the given width, except if a single
+    /// character is wider than the width, in which case the block is widened
+    /// to fit the character.
+    pub fn wrap(text: &str, width: usize) -> Self {
+        Block::wrap_aligned(text, width, TextAlign::Left)
+    }
+
+    /// Create block of given width containing text wrapped into lines, like
+    /// [Block::wrap], with the lines aligned according to given alignment.
+    pub fn wrap_aligned(text: &str, width: usize, align: TextAlign) -> Self {
+        let lines = split_lines(text)
+            .flat_map(|paragraph| {
+                let lines = wrap_paragraph(paragraph, width);
+                let last = lines.len() - 1;
+
+                lines
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, words)| align_line(&words, width, align, index == last))
+                    .collect::<Vec<Block>>()
+            })
+            .collect::<Vec<Block>>();
+
+        // Any too wide character forces all lines to be widened
+        let width = lines.iter().map(Block::width).max().unwrap_or(width);
+        let lines = lines
+            .iter()
+            .flat_map(|line| line.pad_to_width_right(width).lines.to_vec())
+            .collect::<Vec<String>>();
+
+        Block::new(width, lines)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn wrap_on_whitespace() {
+        let b = Block::wrap("The quick  brown fox jumps over the lazy dog", 10);
+
+        assert_eq!(10, b.width());
+        assert_eq!(
+            "The quick\nbrown fox\njumps over\nthe lazy\ndog",
+            b.render()
+        );
+    }
+
+    #[test]
+    fn wrap_keeps_newlines_as_hard_breaks() {
+        let b = Block::wrap("aaa bbb\n\nccc", 20);
+
+        assert_eq!(20, b.width());
+        assert_eq!("aaa bbb\n\nccc", b.render());
+    }
+
+    #[test]
+    fn wrap_breaks_long_words() {
+        let b = Block::wrap("a abcdefgh", 3);
+
+        assert_eq!("a\nabc\ndef\ngh", b.render());
+    }
+
+    #[test]
+    fn wrap_uses_display_width() {
+        let b = Block::wrap("世界世 界", 4);
+
+        assert_eq!(4, b.width());
+        assert_eq!("世界\n世\n界", b.render());
+    }
+
+    #[test]
+    fn wrap_does_not_break_on_non_breaking_space() {
+        let b = Block::wrap("10\u{a0}kg of flour", 6);
+
+        assert_eq!("10\u{a0}kg\nof\nflour", b.render());
+    }
+
+    #[test]
+    fn wrap_aligned_left_and_right() {
+        let left = Block::wrap_aligned("aa b ccc dd", 7, TextAlign::Left);
+        let right = Block::wrap_aligned("aa b ccc dd", 7, TextAlign::Right);
+
+        assert_eq!(vec!["aa b   ", "ccc dd "], *left.lines);
+        assert_eq!(vec!["   aa b", " ccc dd"], *right.lines);
+    }
+
+    #[test]
+    fn wrap_aligned_center() {
+        let left = Block::wrap_aligned("aa b ccc dd", 7, TextAlign::CenterLeft);
+        let right = Block::wrap_aligned("aa b ccc dd", 7, TextAlign::CenterRight);
+
+        assert_eq!(vec![" aa b  ", "ccc dd "], *left.lines);
+        assert_eq!(vec!["  aa b ", " ccc dd"], *right.lines);
+    }
+
+    #[test]
+    fn wrap_aligned_justify() {
+        let b = Block::wrap_aligned("a b c dd ee\nf g", 9, TextAlign::Justify);
+
+        assert_eq!(vec!["a  b c dd", "ee       ", "f g      "], *b.lines);
+    }
+
+    #[test]
+    fn wrap_aligned_justify_single_word() {
+        let b = Block::wrap_aligned("abc defgh", 6, TextAlign::Justify);
+
+        assert_eq!(vec!["abc   ", "defgh "], *b.lines);
+    }
+
+    #[test]
+    fn wrap_widens_block_for_too_wide_character() {
+        let b = Block::wrap("a世", 1);
+
+        assert_eq!(2, b.width());
+        assert_eq!("a\n世", b.render());
+    }
+}